@@ -0,0 +1,86 @@
+use std::error::Error as StdError;
+use tokio::fs::{read_to_string, write};
+
+use crate::config::Config;
+use crate::utils::catalog_io::{parse_catalog, CatalogFormat};
+use crate::utils::error::CustomError;
+use crate::utils::product_store::ProductStore;
+
+const CATALOG_USAGE: &str = "Usage:
+  SakuraServe catalog import <file> [--format csv|jsonl] [--dry-run]
+  SakuraServe catalog export <file|-> [--format csv|jsonl]";
+
+struct CatalogArgs {
+    command: String,
+    path: String,
+    format: Option<CatalogFormat>,
+    dry_run: bool,
+}
+
+fn parse_catalog_args(args: &[String]) -> Result<CatalogArgs, CustomError> {
+    let usage = || CustomError::new(CATALOG_USAGE, "INVALID_ARGUMENTS");
+
+    let mut positional = Vec::new();
+    let mut format = None;
+    let mut dry_run = false;
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--dry-run" => dry_run = true,
+            "--format" => {
+                let name = iter.next().ok_or_else(usage)?;
+                format = Some(CatalogFormat::from_name(name).ok_or_else(usage)?);
+            }
+            _ => positional.push(arg.clone()),
+        }
+    }
+
+    match positional.as_slice() {
+        [command, path] if command == "import" || command == "export" => Ok(CatalogArgs {
+            command: command.clone(),
+            path: path.clone(),
+            format,
+            dry_run,
+        }),
+        _ => Err(usage()),
+    }
+}
+
+pub async fn run_catalog_command(args: &[String], config: &Config) -> Result<(), Box<dyn StdError>> {
+    let args = parse_catalog_args(args)?;
+
+    let format = args.format
+        .or_else(|| CatalogFormat::from_path(&args.path))
+        .ok_or_else(|| CustomError::new("Cannot detect the catalog format, pass --format csv|jsonl", "UNSUPPORTED_CATALOG_FORMAT"))?;
+
+    let products_store = ProductStore::new(config.products_file_path.clone(), config.low_stock_threshold).await?;
+
+    if args.command == "export" {
+        let data = products_store.export_products(format).await?;
+
+        if args.path == "-" {
+            print!("{}", data);
+        } else {
+            write(&args.path, data).await?;
+            eprintln!("Exported catalog to {}", args.path);
+        }
+
+        return Ok(());
+    }
+
+    let data = read_to_string(&args.path).await?;
+    let parsed = parse_catalog(format, &data)?;
+    let report = products_store.import_products(parsed, args.dry_run).await?;
+
+    println!("{}", serde_json::to_string_pretty(&report)?);
+
+    if report.errors.is_empty() {
+        Ok(())
+    } else {
+        Err(Box::new(CustomError::new(
+            &format!("{} row(s) failed validation, the catalog was not changed", report.errors.len()),
+            "IMPORT_ROWS_INVALID",
+        )))
+    }
+}
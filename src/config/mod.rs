@@ -9,6 +9,23 @@ pub struct Config {
     pub favorites_file_path: String,
     pub orders_file_path: String,
     pub promocodes_file_path: String,
+    pub exchange_rates_file_path: String,
+    pub reviews_file_path: String,
+    pub banned_words_file_path: String,
+    pub categories_file_path: String,
+    pub recently_viewed_file_path: String,
+    pub comparisons_file_path: String,
+    pub share_links_file_path: String,
+    pub share_link_secret: Option<String>,
+    pub data_watch_interval_secs: u64,
+    pub low_stock_threshold: u32,
+    pub recently_viewed_limit: usize,
+    pub recently_viewed_flush_interval_secs: u64,
+    pub comparison_limit: usize,
+    pub cart_expiry_days: u32,
+    pub guest_cart_expiry_days: u32,
+    pub cart_prune_interval_secs: u64,
+    pub share_link_expiry_days: u32,
 }
 
 impl Config {
@@ -23,6 +40,23 @@ impl Config {
             favorites_file_path: env::var("DATA_FAVORITES_FILE_PATH").unwrap_or_else(|_| "data/db/favorites.json".to_string()),
             orders_file_path: env::var("DATA_ORDERS_FILE_PATH").unwrap_or_else(|_| "data/db/orders.json".to_string()),
             promocodes_file_path: env::var("DATA_PROMOCODES_FILE_PATH").unwrap_or_else(|_| "data/db/promocodes.json".to_string()),
+            exchange_rates_file_path: env::var("DATA_EXCHANGE_RATES_FILE_PATH").unwrap_or_else(|_| "data/db/exchange_rates.json".to_string()),
+            reviews_file_path: env::var("DATA_REVIEWS_FILE_PATH").unwrap_or_else(|_| "data/db/reviews.json".to_string()),
+            banned_words_file_path: env::var("DATA_BANNED_WORDS_FILE_PATH").unwrap_or_else(|_| "data/db/banned_words.json".to_string()),
+            categories_file_path: env::var("DATA_CATEGORIES_FILE_PATH").unwrap_or_else(|_| "data/db/categories.json".to_string()),
+            recently_viewed_file_path: env::var("DATA_RECENTLY_VIEWED_FILE_PATH").unwrap_or_else(|_| "data/db/recently_viewed.json".to_string()),
+            comparisons_file_path: env::var("DATA_COMPARISONS_FILE_PATH").unwrap_or_else(|_| "data/db/comparisons.json".to_string()),
+            share_links_file_path: env::var("DATA_SHARE_LINKS_FILE_PATH").unwrap_or_else(|_| "data/db/share_links.json".to_string()),
+            share_link_secret: env::var("SHARE_LINK_SECRET").ok().filter(|v| !v.is_empty()),
+            data_watch_interval_secs: env::var("DATA_WATCH_INTERVAL_SECS").ok().and_then(|v| v.parse().ok()).unwrap_or(2),
+            low_stock_threshold: env::var("LOW_STOCK_THRESHOLD").ok().and_then(|v| v.parse().ok()).unwrap_or(5),
+            recently_viewed_limit: env::var("RECENTLY_VIEWED_LIMIT").ok().and_then(|v| v.parse().ok()).unwrap_or(20),
+            recently_viewed_flush_interval_secs: env::var("RECENTLY_VIEWED_FLUSH_INTERVAL_SECS").ok().and_then(|v| v.parse().ok()).unwrap_or(30).max(1),
+            comparison_limit: env::var("COMPARISON_LIMIT").ok().and_then(|v| v.parse().ok()).unwrap_or(4),
+            cart_expiry_days: env::var("CART_EXPIRY_DAYS").ok().and_then(|v| v.parse().ok()).unwrap_or(30),
+            guest_cart_expiry_days: env::var("GUEST_CART_EXPIRY_DAYS").ok().and_then(|v| v.parse().ok()).unwrap_or(7),
+            cart_prune_interval_secs: env::var("CART_PRUNE_INTERVAL_SECS").ok().and_then(|v| v.parse().ok()).unwrap_or(3600),
+            share_link_expiry_days: env::var("SHARE_LINK_EXPIRY_DAYS").ok().and_then(|v| v.parse().ok()).unwrap_or(7),
         })
     }
 }
@@ -0,0 +1,226 @@
+use actix_session::Session;
+use actix_web::{delete, get, patch, post, web, HttpRequest, HttpResponse, Responder};
+use serde::Deserialize;
+use serde_json::json;
+use std::error::Error as StdError;
+use uuid::Uuid;
+
+use crate::models::product::{ProductDraft, ProductPatch};
+use crate::state::app_state::AppState;
+use crate::utils::catalog_io::{parse_catalog, CatalogFormat};
+use crate::utils::error::CustomError;
+use crate::utils::func::require_admin;
+
+#[post("/")]
+pub async fn create_product(
+    session: Session,
+    data: web::Json<ProductDraft>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    if let Err(response) = require_admin(&session, &app_state).await {
+        return response;
+    }
+
+    match app_state.products_store.create_product(data.into_inner()).await {
+        Ok(product) => HttpResponse::Created().json(json!({
+            "message": "Product created successfully",
+            "errorCode": "SUCCESS",
+            "product": product
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+#[patch("/{product_id}")]
+pub async fn update_product(
+    session: Session,
+    path: web::Path<Uuid>,
+    data: web::Json<ProductPatch>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    if let Err(response) = require_admin(&session, &app_state).await {
+        return response;
+    }
+
+    let product_id = path.into_inner();
+
+    match app_state.products_store.update_product(product_id, data.into_inner()).await {
+        Ok(product) => HttpResponse::Ok().json(json!({
+            "message": "Product updated successfully",
+            "errorCode": "SUCCESS",
+            "product": product
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+#[delete("/{product_id}")]
+pub async fn delete_product(
+    session: Session,
+    path: web::Path<Uuid>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    if let Err(response) = require_admin(&session, &app_state).await {
+        return response;
+    }
+
+    let product_id = path.into_inner();
+
+    match app_state.products_store.delete_product(product_id).await {
+        Ok(_) => HttpResponse::Ok().json(json!({
+            "message": "Product deleted successfully",
+            "errorCode": "SUCCESS"
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+#[post("/bulk")]
+pub async fn upsert_products(
+    session: Session,
+    data: web::Json<Vec<ProductDraft>>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    if let Err(response) = require_admin(&session, &app_state).await {
+        return response;
+    }
+
+    match app_state.products_store.upsert_products(data.into_inner()).await {
+        Ok(result) => HttpResponse::Ok().json(json!({
+            "message": "Products imported successfully",
+            "errorCode": "SUCCESS",
+            "result": result
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ImportQuery {
+    pub format: Option<String>,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[post("/import")]
+pub async fn import_products(
+    session: Session,
+    req: HttpRequest,
+    query: web::Query<ImportQuery>,
+    body: String,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    if let Err(response) = require_admin(&session, &app_state).await {
+        return response;
+    }
+
+    let content_type = req
+        .headers()
+        .get("Content-Type")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next());
+
+    let format = match query.format.as_deref().or(content_type).and_then(CatalogFormat::from_name) {
+        Some(format) => format,
+        None => return unsupported_format(),
+    };
+
+    let parsed = match parse_catalog(format, &body) {
+        Ok(parsed) => parsed,
+        Err(e) => return error_response(Box::new(e)),
+    };
+
+    match app_state.products_store.import_products(parsed, query.dry_run).await {
+        Ok(report) if !report.dry_run && !report.errors.is_empty() => HttpResponse::UnprocessableEntity().json(json!({
+            "message": "Import contains invalid rows, the catalog was not changed",
+            "errorCode": "IMPORT_ROWS_INVALID",
+            "report": report
+        })),
+        Ok(report) => HttpResponse::Ok().json(json!({
+            "message": if report.dry_run { "Dry run completed" } else { "Products imported successfully" },
+            "errorCode": "SUCCESS",
+            "report": report
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ExportQuery {
+    pub format: Option<String>,
+}
+
+#[get("/export")]
+pub async fn export_products(
+    session: Session,
+    query: web::Query<ExportQuery>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    if let Err(response) = require_admin(&session, &app_state).await {
+        return response;
+    }
+
+    let format = match CatalogFormat::from_name(query.format.as_deref().unwrap_or("csv")) {
+        Some(format) => format,
+        None => return unsupported_format(),
+    };
+
+    match app_state.products_store.export_products(format).await {
+        Ok(data) => HttpResponse::Ok()
+            .content_type(format.content_type())
+            .insert_header((
+                "Content-Disposition",
+                format!("attachment; filename=\"products.{}\"", format.extension()),
+            ))
+            .body(data),
+        Err(e) => error_response(e),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct LowStockQuery {
+    pub threshold: Option<u32>,
+}
+
+#[get("/low-stock")]
+pub async fn get_low_stock_products(
+    session: Session,
+    query: web::Query<LowStockQuery>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    if let Err(response) = require_admin(&session, &app_state).await {
+        return response;
+    }
+
+    let threshold = query.threshold.unwrap_or(app_state.products_store.low_stock_threshold);
+
+    let products = app_state.products_store.low_stock(threshold).await;
+    HttpResponse::Ok().json(json!(products))
+}
+
+fn unsupported_format() -> HttpResponse {
+    HttpResponse::BadRequest().json(json!({
+        "message": "Catalog format must be `csv` or `jsonl`",
+        "errorCode": "UNSUPPORTED_CATALOG_FORMAT"
+    }))
+}
+
+fn error_response(e: Box<dyn StdError>) -> HttpResponse {
+    match e.downcast_ref::<CustomError>() {
+        Some(error) if error.error_code == "PRODUCT_NOT_FOUND" => HttpResponse::NotFound().json(json!({
+            "message": error.message,
+            "errorCode": error.error_code
+        })),
+        Some(error) => HttpResponse::BadRequest().json(json!({
+            "message": error.message,
+            "errorCode": error.error_code
+        })),
+        None => {
+            log::error!("Failed to update product catalog: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "message": "Failed to save products",
+                "errorCode": "SAVE_ERROR"
+            }))
+        }
+    }
+}
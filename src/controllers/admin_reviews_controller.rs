@@ -0,0 +1,79 @@
+use actix_session::Session;
+use actix_web::{get, post, web, HttpResponse, Responder};
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::controllers::reviews_controller::{error_response, ReviewsPageQuery};
+use crate::state::app_state::AppState;
+use crate::utils::func::require_admin;
+use crate::utils::reviews_store::ModerationFilter;
+
+const DEFAULT_PAGE_LIMIT: usize = 20;
+const MAX_PAGE_LIMIT: usize = 100;
+
+#[derive(Deserialize)]
+pub struct RejectRequest {
+    pub reason: String,
+}
+
+#[get("/")]
+pub async fn get_moderation_queue(
+    session: Session,
+    filter: web::Query<ModerationFilter>,
+    query: web::Query<ReviewsPageQuery>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    if let Err(response) = require_admin(&session, &app_state).await {
+        return response;
+    }
+
+    let page = query.page.unwrap_or(1);
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+
+    let result = app_state.reviews_store.moderation_queue(&filter, page, limit).await;
+    HttpResponse::Ok().json(json!(result))
+}
+
+#[post("/{review_id}/approve")]
+pub async fn approve_review(
+    session: Session,
+    path: web::Path<Uuid>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let admin_id = match require_admin(&session, &app_state).await {
+        Ok(admin_id) => admin_id,
+        Err(response) => return response,
+    };
+
+    match app_state.reviews_store.approve_review(admin_id, path.into_inner()).await {
+        Ok(review) => HttpResponse::Ok().json(json!({
+            "message": "Review approved",
+            "errorCode": "SUCCESS",
+            "review": review
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+#[post("/{review_id}/reject")]
+pub async fn reject_review(
+    session: Session,
+    path: web::Path<Uuid>,
+    data: web::Json<RejectRequest>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let admin_id = match require_admin(&session, &app_state).await {
+        Ok(admin_id) => admin_id,
+        Err(response) => return response,
+    };
+
+    match app_state.reviews_store.reject_review(admin_id, path.into_inner(), &data.reason).await {
+        Ok(review) => HttpResponse::Ok().json(json!({
+            "message": "Review rejected",
+            "errorCode": "SUCCESS",
+            "review": review
+        })),
+        Err(e) => error_response(e),
+    }
+}
@@ -50,8 +50,8 @@ pub async fn sign_up(
         }));
     }
 
-    if app_state.users_store.find_user_by_username(&username).await.is_some()
-        || app_state.users_store.find_user_by_login_or_email(&login).await.is_some()
+    if app_state.users_store.find_user_by_username(username).await.is_some()
+        || app_state.users_store.find_user_by_login_or_email(login).await.is_some()
     {
         log::error!("User already exists: username = {}, login = {}", username, login);
         return HttpResponse::BadRequest().json(json!({
@@ -90,6 +90,8 @@ pub async fn sign_up(
         address: Some(data.address.clone()),
         zip_code: Some(data.zip_code.clone()),
         credit_cards: Some(Vec::new()),
+        preferred_currency: None,
+        is_admin: false,
     };
 
     log::info!("Adding user to store: {}", username);
@@ -152,6 +154,15 @@ pub async fn sign_in(
 
             session.insert("user_id", user.id).unwrap();
 
+            if let Some(guest_id) = session.remove_as::<Uuid>("guest_id").and_then(Result::ok) {
+                if let Err(e) = app_state.carts_store.merge_guest_cart(guest_id, user.id).await {
+                    log::error!("Failed to merge guest cart: {}", e);
+                }
+                if let Err(e) = app_state.recently_viewed_store.merge_guest_history(guest_id, user.id).await {
+                    log::error!("Failed to merge recently viewed products: {}", e);
+                }
+            }
+
             HttpResponse::Ok().json(json!({
                 "message": "Logged in successfully",
                 "errorCode": "SUCCESS"
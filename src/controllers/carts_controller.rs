@@ -1,23 +1,200 @@
 use actix_session::Session;
-use actix_web::{delete, get, post, web, HttpResponse, Responder};
+use actix_web::{delete, get, patch, post, put, web, HttpResponse, Responder};
+use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::error::Error as StdError;
 use uuid::Uuid;
 
 use crate::state::app_state::AppState;
+use crate::utils::cart_store::{CartChange, CartQuantityRequest, CartUpdateRequest};
+use crate::utils::error::CustomError;
+use crate::utils::func::{current_visitor, display_currency, localize_amounts, localize_products, CurrencyQuery};
+use crate::utils::pricing::{checkout_currency, price_items, PriceSummary};
+
+#[derive(Deserialize)]
+pub struct CartSummaryQuery {
+    pub promo_code: Option<String>,
+    pub product_ids: Option<String>,
+}
+
+#[derive(Serialize)]
+struct CartSummaryResponse {
+    #[serde(flatten)]
+    summary: PriceSummary,
+    changes: Vec<CartChange>,
+}
 
 #[get("/")]
 pub async fn get_cart(
     session: Session,
+    currency: web::Query<CurrencyQuery>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let visitor = current_visitor(&session);
+    let mut cart = app_state.carts_store.validate_cart(visitor.id, None).await;
+
+    let localized = match localize_products(cart.items.iter_mut().map(|item| &mut item.product), &session, &currency, &app_state).await {
+        Ok(()) => localize_amounts(cart.changes.iter_mut().flat_map(CartChange::amounts_mut), &session, &currency, &app_state).await,
+        Err(e) => Err(e),
+    };
+
+    if let Err(e) = localized {
+        return HttpResponse::BadRequest().json(json!({
+            "message": e.message,
+            "errorCode": e.error_code
+        }));
+    }
+
+    HttpResponse::Ok().json(json!(cart))
+}
+
+#[get("/summary")]
+pub async fn get_cart_summary(
+    session: Session,
+    query: web::Query<CartSummaryQuery>,
+    currency: web::Query<CurrencyQuery>,
     app_state: web::Data<AppState>
 ) -> impl Responder {
-    if let Some(user_id) = session.get::<Uuid>("user_id").unwrap_or(None) {
-        let cart = app_state.carts_store.get_cart(user_id).await;
-        HttpResponse::Ok().json(json!(cart))
+    let visitor = current_visitor(&session);
+
+    let product_ids = match query.product_ids.as_deref().filter(|ids| !ids.trim().is_empty()) {
+        Some(ids) => match ids.split(',').map(|id| id.trim().parse::<Uuid>()).collect::<Result<Vec<_>, _>>() {
+            Ok(ids) => Some(ids),
+            Err(_) => return HttpResponse::BadRequest().json(json!({
+                "message": "Invalid product id in `product_ids`",
+                "errorCode": "INVALID_PRODUCT_ID"
+            })),
+        },
+        None => None,
+    };
+
+    let mut cart = app_state.carts_store.validate_cart(visitor.id, product_ids.as_deref()).await;
+
+    let preferred = if visitor.guest {
+        display_currency(&session, &currency, &app_state).await
     } else {
-        HttpResponse::Unauthorized().json(json!({
-            "message": "Unauthorized",
-            "errorCode": "UNAUTHORIZED_ACCESS"
-        }))
+        Ok(app_state.users_store.find_user_by_id(visitor.id).await.and_then(|u| u.preferred_currency))
+    };
+    let preferred = match preferred {
+        Ok(preferred) => preferred,
+        Err(e) => return HttpResponse::BadRequest().json(json!({
+            "message": e.message,
+            "errorCode": e.error_code
+        })),
+    };
+
+    let currency = checkout_currency(preferred.as_deref(), &cart.items);
+    let rates = app_state.exchange_rates_store.get_rates().await;
+
+    for amount in cart.changes.iter_mut().flat_map(CartChange::amounts_mut) {
+        match rates.convert(amount, &currency) {
+            Ok(converted) => *amount = converted,
+            Err(e) => return error_response(Box::new(e)),
+        }
+    }
+
+    let mut summary = match price_items(cart.items, &currency, &rates) {
+        Ok(summary) => summary,
+        Err(e) => return error_response(Box::new(e)),
+    };
+
+    if let Some(code) = query.promo_code.as_deref().filter(|code| !code.is_empty()) {
+        if let Err(e) = summary.preview_promo_code(code, &app_state.promocodes_store).await {
+            return error_response(e);
+        }
+    }
+
+    HttpResponse::Ok().json(CartSummaryResponse { summary, changes: cart.changes })
+}
+
+#[post("/acknowledge")]
+pub async fn acknowledge_cart_changes(
+    session: Session,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let visitor = current_visitor(&session);
+
+    match app_state.carts_store.acknowledge_changes(visitor.id).await {
+        Ok(()) => HttpResponse::Ok().json(json!({
+            "message": "Cart changes acknowledged",
+            "errorCode": "SUCCESS"
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+#[get("/saved")]
+pub async fn get_saved_for_later(
+    session: Session,
+    currency: web::Query<CurrencyQuery>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let visitor = current_visitor(&session);
+    let mut saved = app_state.carts_store.validate_saved_for_later(visitor.id).await;
+
+    let localized = match localize_products(saved.items.iter_mut().map(|item| &mut item.product), &session, &currency, &app_state).await {
+        Ok(()) => localize_amounts(saved.changes.iter_mut().flat_map(CartChange::amounts_mut), &session, &currency, &app_state).await,
+        Err(e) => Err(e),
+    };
+
+    if let Err(e) = localized {
+        return HttpResponse::BadRequest().json(json!({
+            "message": e.message,
+            "errorCode": e.error_code
+        }));
+    }
+
+    HttpResponse::Ok().json(json!(saved))
+}
+
+#[post("/saved/{product_id}")]
+pub async fn save_for_later(
+    session: Session,
+    path: web::Path<Uuid>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let visitor = current_visitor(&session);
+
+    match app_state.carts_store.save_for_later(visitor.id, path.into_inner()).await {
+        Ok(()) => HttpResponse::Ok().json(json!({
+            "message": "Product saved for later",
+            "errorCode": "SUCCESS"
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+#[post("/saved/{product_id}/restore")]
+pub async fn move_to_cart(
+    session: Session,
+    path: web::Path<Uuid>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let visitor = current_visitor(&session);
+
+    match app_state.carts_store.move_to_cart(visitor.id, path.into_inner()).await {
+        Ok(()) => HttpResponse::Ok().json(json!({
+            "message": "Product moved to cart",
+            "errorCode": "SUCCESS"
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+#[delete("/saved/{product_id}")]
+pub async fn remove_saved_item(
+    session: Session,
+    path: web::Path<Uuid>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let visitor = current_visitor(&session);
+
+    match app_state.carts_store.remove_saved_item(visitor.id, path.into_inner()).await {
+        Ok(()) => HttpResponse::Ok().json(json!({
+            "message": "Product removed from saved for later",
+            "errorCode": "SUCCESS"
+        })),
+        Err(e) => error_response(e),
     }
 }
 
@@ -28,23 +205,14 @@ pub async fn add_product_to_cart(
     app_state: web::Data<AppState>
 ) -> impl Responder {
     let product_id = path.into_inner();
+    let visitor = current_visitor(&session);
 
-    if let Some(user_id) = session.get::<Uuid>("user_id").unwrap_or(None) {
-        match app_state.carts_store.add_product_to_cart(user_id, product_id).await {
-            Ok(_) => HttpResponse::Ok().json(json!({
-                "message": "Product added to cart successfully",
-                "errorCode": "SUCCESS"
-            })),
-            Err(e) => HttpResponse::BadRequest().json(json!({
-                "message": e.to_string(),
-                "errorCode": "BAD_REQUEST_ERROR"
-            })),
-        }
-    } else {
-        HttpResponse::Unauthorized().json(json!({
-            "message": "Unauthorized",
-            "errorCode": "UNAUTHORIZED_ACCESS"
-        }))
+    match app_state.carts_store.add_product_to_cart(visitor.id, visitor.guest, product_id).await {
+        Ok(_) => HttpResponse::Ok().json(json!({
+            "message": "Product added to cart successfully",
+            "errorCode": "SUCCESS"
+        })),
+        Err(e) => error_response(e),
     }
 }
 
@@ -55,22 +223,88 @@ pub async fn remove_product_from_cart(
     app_state: web::Data<AppState>
 ) -> impl Responder {
     let product_id = path.into_inner();
+    let visitor = current_visitor(&session);
 
-    if let Some(user_id) = session.get::<Uuid>("user_id").unwrap_or(None) {
-        match app_state.carts_store.remove_product_from_cart(user_id, product_id).await {
-            Ok(_) => HttpResponse::Ok().json(json!({
-                "message": "Product removed from cart successfully",
-                "errorCode": "SUCCESS"
-            })),
-            Err(e) => HttpResponse::BadRequest().json(json!({
-                "message": e.to_string(),
-                "errorCode": "BAD_REQUEST_ERROR"
-            })),
+    match app_state.carts_store.remove_product_from_cart(visitor.id, product_id).await {
+        Ok(_) => HttpResponse::Ok().json(json!({
+            "message": "Product removed from cart successfully",
+            "errorCode": "SUCCESS"
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+#[put("/{product_id}")]
+pub async fn set_cart_item_quantity(
+    session: Session,
+    path: web::Path<Uuid>,
+    data: web::Json<CartQuantityRequest>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let visitor = current_visitor(&session);
+
+    match app_state.carts_store.set_product_quantity(visitor.id, visitor.guest, path.into_inner(), data.count).await {
+        Ok(()) => HttpResponse::Ok().json(json!({
+            "message": "Cart item quantity updated successfully",
+            "errorCode": "SUCCESS"
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+#[patch("/")]
+pub async fn update_cart(
+    session: Session,
+    data: web::Json<CartUpdateRequest>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let visitor = current_visitor(&session);
+
+    match app_state.carts_store.update_cart(visitor.id, visitor.guest, data.into_inner().items).await {
+        Ok(()) => HttpResponse::Ok().json(json!({
+            "message": "Cart updated successfully",
+            "errorCode": "SUCCESS"
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+#[delete("/")]
+pub async fn clear_cart(
+    session: Session,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let visitor = current_visitor(&session);
+
+    match app_state.carts_store.clear_cart(visitor.id).await {
+        Ok(()) => HttpResponse::Ok().json(json!({
+            "message": "Cart cleared successfully",
+            "errorCode": "SUCCESS"
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+fn error_response(e: Box<dyn StdError>) -> HttpResponse {
+    match e.downcast_ref::<CustomError>() {
+        Some(error) => {
+            let mut response = match error.error_code.as_str() {
+                "PRODUCT_NOT_FOUND" | "USER_CART_NOT_FOUND" | "PRODUCT_NOT_IN_CART" | "PRODUCT_NOT_SAVED" => HttpResponse::NotFound(),
+                "OUT_OF_STOCK" => HttpResponse::Conflict(),
+                _ => HttpResponse::BadRequest(),
+            };
+
+            response.json(json!({
+                "message": error.message,
+                "errorCode": error.error_code
+            }))
+        }
+        None => {
+            log::error!("Cart request failed: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "message": "Failed to process the cart request",
+                "errorCode": "INTERNAL_ERROR"
+            }))
         }
-    } else {
-        HttpResponse::Unauthorized().json(json!({
-            "message": "Unauthorized",
-            "errorCode": "UNAUTHORIZED_ACCESS"
-        }))
     }
 }
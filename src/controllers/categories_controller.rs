@@ -0,0 +1,27 @@
+use actix_web::{get, web, HttpResponse, Responder};
+use serde_json::json;
+
+use crate::state::app_state::AppState;
+
+#[get("/")]
+pub async fn get_categories(app_state: web::Data<AppState>) -> impl Responder {
+    let products = app_state.products_store.group_tags().await;
+    let categories = app_state.categories_store.get_tree(&products).await;
+    HttpResponse::Ok().json(json!(categories))
+}
+
+#[get("/{slug}")]
+pub async fn get_category(
+    path: web::Path<String>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let products = app_state.products_store.group_tags().await;
+
+    match app_state.categories_store.get_category(&path.into_inner(), &products).await {
+        Some(category) => HttpResponse::Ok().json(json!(category)),
+        None => HttpResponse::NotFound().json(json!({
+            "message": "Category not found",
+            "errorCode": "CATEGORY_NOT_FOUND"
+        })),
+    }
+}
@@ -0,0 +1,119 @@
+use actix_session::Session;
+use actix_web::{delete, get, post, web, HttpResponse, Responder};
+use serde_json::json;
+use std::error::Error as StdError;
+use uuid::Uuid;
+
+use crate::state::app_state::AppState;
+use crate::utils::comparisons_store::ProductComparison;
+use crate::utils::error::CustomError;
+use crate::utils::func::{localize_products, CurrencyQuery};
+
+fn unauthorized() -> HttpResponse {
+    HttpResponse::Unauthorized().json(json!({
+        "message": "Unauthorized",
+        "errorCode": "UNAUTHORIZED_ACCESS"
+    }))
+}
+
+#[get("/")]
+pub async fn get_comparison(
+    session: Session,
+    currency: web::Query<CurrencyQuery>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let Some(user_id) = session.get::<Uuid>("user_id").unwrap_or(None) else {
+        return unauthorized();
+    };
+
+    let mut products = app_state.comparisons_store.get_products(user_id).await;
+
+    if let Err(e) = localize_products(products.iter_mut(), &session, &currency, &app_state).await {
+        return HttpResponse::BadRequest().json(json!({
+            "message": e.message,
+            "errorCode": e.error_code
+        }));
+    }
+
+    HttpResponse::Ok().json(ProductComparison::new(&products, app_state.comparisons_store.limit))
+}
+
+#[post("/add/{product_id}")]
+pub async fn add_product_to_comparison(
+    session: Session,
+    path: web::Path<Uuid>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let Some(user_id) = session.get::<Uuid>("user_id").unwrap_or(None) else {
+        return unauthorized();
+    };
+
+    match app_state.comparisons_store.add_product(user_id, path.into_inner()).await {
+        Ok(()) => HttpResponse::Ok().json(json!({
+            "message": "Product added to comparison successfully",
+            "errorCode": "SUCCESS"
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+#[delete("/{product_id}")]
+pub async fn remove_product_from_comparison(
+    session: Session,
+    path: web::Path<Uuid>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let Some(user_id) = session.get::<Uuid>("user_id").unwrap_or(None) else {
+        return unauthorized();
+    };
+
+    match app_state.comparisons_store.remove_product(user_id, path.into_inner()).await {
+        Ok(()) => HttpResponse::Ok().json(json!({
+            "message": "Product removed from comparison successfully",
+            "errorCode": "SUCCESS"
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+#[delete("/")]
+pub async fn clear_comparison(
+    session: Session,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let Some(user_id) = session.get::<Uuid>("user_id").unwrap_or(None) else {
+        return unauthorized();
+    };
+
+    match app_state.comparisons_store.clear(user_id).await {
+        Ok(()) => HttpResponse::Ok().json(json!({
+            "message": "Comparison cleared",
+            "errorCode": "SUCCESS"
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+fn error_response(e: Box<dyn StdError>) -> HttpResponse {
+    match e.downcast_ref::<CustomError>() {
+        Some(error) => {
+            let mut response = match error.error_code.as_str() {
+                "PRODUCT_NOT_FOUND" | "PRODUCT_NOT_FOUND_IN_COMPARISON" => HttpResponse::NotFound(),
+                "PRODUCT_ALREADY_IN_COMPARISON" | "COMPARISON_LIMIT_REACHED" => HttpResponse::Conflict(),
+                _ => HttpResponse::BadRequest(),
+            };
+
+            response.json(json!({
+                "message": error.message,
+                "errorCode": error.error_code
+            }))
+        }
+        None => {
+            log::error!("Failed to save comparison: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "message": "Failed to save comparison",
+                "errorCode": "SAVE_ERROR"
+            }))
+        }
+    }
+}
@@ -0,0 +1,10 @@
+use actix_web::{get, web, HttpResponse, Responder};
+use serde_json::json;
+
+use crate::state::app_state::AppState;
+
+#[get("/")]
+pub async fn get_exchange_rates(app_state: web::Data<AppState>) -> impl Responder {
+    let rates = app_state.exchange_rates_store.get_rates().await;
+    HttpResponse::Ok().json(json!(rates))
+}
@@ -4,14 +4,24 @@ use serde_json::json;
 use uuid::Uuid;
 
 use crate::state::app_state::AppState;
+use crate::utils::func::{localize_products, CurrencyQuery};
 
 #[get("/")]
 pub async fn get_favorites(
     session: Session,
+    currency: web::Query<CurrencyQuery>,
     app_state: web::Data<AppState>
 ) -> impl Responder {
     if let Some(user_id) = session.get::<Uuid>("user_id").unwrap_or(None) {
-        let favorites = app_state.favorites_store.get_favorites(user_id).await;
+        let mut favorites = app_state.favorites_store.get_favorites(user_id).await;
+
+        if let Err(e) = localize_products(favorites.iter_mut(), &session, &currency, &app_state).await {
+            return HttpResponse::BadRequest().json(json!({
+                "message": e.message,
+                "errorCode": e.error_code
+            }));
+        }
+
         HttpResponse::Ok().json(json!(favorites))
     } else {
         HttpResponse::Unauthorized().json(json!({
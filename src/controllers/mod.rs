@@ -3,4 +3,14 @@ pub mod users_controller;
 pub mod carts_controller;
 pub mod favorites_controller;
 pub mod orders_controller;
-pub mod promocodes_controller;
\ No newline at end of file
+pub mod promocodes_controller;
+pub mod products_controller;
+pub mod currencies_controller;
+pub mod admin_products_controller;
+pub mod reviews_controller;
+pub mod admin_reviews_controller;
+pub mod categories_controller;
+pub mod recommendations_controller;
+pub mod recently_viewed_controller;
+pub mod comparisons_controller;
+pub mod share_links_controller;
\ No newline at end of file
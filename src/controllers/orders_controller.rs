@@ -5,6 +5,7 @@ use serde_json::json;
 use uuid::Uuid;
 
 use crate::state::app_state::AppState;
+use crate::utils::error::CustomError;
 
 #[derive(Deserialize)]
 pub struct OrderRequest {
@@ -45,6 +46,21 @@ pub async fn create_order(
                 "message": "Order created successfully",
                 "errorCode": "SUCCESS"
             })),
+            Err(e) if e.downcast_ref::<CustomError>().is_some_and(|e| e.error_code == "CART_CHANGED") => {
+                let cart = app_state.carts_store.validate_cart(user_id, Some(&data.product_ids)).await;
+
+                HttpResponse::Conflict().json(json!({
+                    "message": e.to_string(),
+                    "errorCode": "CART_CHANGED",
+                    "changes": cart.changes
+                }))
+            }
+            Err(e) if e.downcast_ref::<CustomError>().is_some_and(|e| e.error_code == "OUT_OF_STOCK") => {
+                HttpResponse::Conflict().json(json!({
+                    "message": e.to_string(),
+                    "errorCode": "OUT_OF_STOCK"
+                }))
+            }
             Err(e) => HttpResponse::BadRequest().json(json!({
                 "message": e.to_string(),
                 "errorCode": "BAD_REQUEST_ERROR"
@@ -84,3 +100,57 @@ pub async fn delete_order(
         }))
     }
 }
+
+#[post("/{order_id}/cancel")]
+pub async fn cancel_order(
+    session: Session,
+    path: web::Path<Uuid>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let order_id = path.into_inner();
+
+    if let Some(user_id) = session.get::<Uuid>("user_id").unwrap_or(None) {
+        match app_state.orders_store.cancel_order(user_id, order_id).await {
+            Ok(_) => HttpResponse::Ok().json(json!({
+                "message": "Order canceled successfully",
+                "errorCode": "SUCCESS"
+            })),
+            Err(e) => HttpResponse::BadRequest().json(json!({
+                "message": e.to_string(),
+                "errorCode": "BAD_REQUEST_ERROR"
+            })),
+        }
+    } else {
+        HttpResponse::Unauthorized().json(json!({
+            "message": "Unauthorized",
+            "errorCode": "UNAUTHORIZED_ACCESS"
+        }))
+    }
+}
+
+#[post("/{order_id}/return")]
+pub async fn return_order(
+    session: Session,
+    path: web::Path<Uuid>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let order_id = path.into_inner();
+
+    if let Some(user_id) = session.get::<Uuid>("user_id").unwrap_or(None) {
+        match app_state.orders_store.return_order(user_id, order_id).await {
+            Ok(_) => HttpResponse::Ok().json(json!({
+                "message": "Order returned successfully",
+                "errorCode": "SUCCESS"
+            })),
+            Err(e) => HttpResponse::BadRequest().json(json!({
+                "message": e.to_string(),
+                "errorCode": "BAD_REQUEST_ERROR"
+            })),
+        }
+    } else {
+        HttpResponse::Unauthorized().json(json!({
+            "message": "Unauthorized",
+            "errorCode": "UNAUTHORIZED_ACCESS"
+        }))
+    }
+}
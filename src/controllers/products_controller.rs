@@ -0,0 +1,249 @@
+use actix_session::Session;
+use actix_web::{get, web, HttpRequest, HttpResponse, Responder};
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::models::product::{ProductFilter, ProductSort};
+use crate::state::app_state::AppState;
+use crate::utils::error::CustomError;
+use crate::utils::func::{current_visitor, display_currency, is_crawler, localize_products, CurrencyQuery};
+use crate::utils::product_store::ProductView;
+
+const DEFAULT_PAGE_LIMIT: usize = 20;
+const MAX_PAGE_LIMIT: usize = 100;
+const DEFAULT_SUGGESTION_LIMIT: usize = 5;
+const MAX_SUGGESTION_LIMIT: usize = 20;
+
+#[derive(Deserialize)]
+pub struct PageQuery {
+    pub page: Option<usize>,
+    pub limit: Option<usize>,
+    pub sort: Option<ProductSort>,
+}
+
+#[get("/")]
+pub async fn get_products(
+    session: Session,
+    filter: web::Query<ProductFilter>,
+    query: web::Query<PageQuery>,
+    currency: web::Query<CurrencyQuery>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let page = query.page.unwrap_or(1);
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+
+    let filter = match with_category(filter.into_inner(), &app_state).await {
+        Ok(filter) => filter,
+        Err(response) => return response,
+    };
+
+    let filter = match with_price_currency(filter, &session, &currency, &app_state).await {
+        Ok(filter) => filter,
+        Err(e) => return currency_error(e),
+    };
+
+    let mut result = app_state.products_store.query_products(&filter, query.sort, page, limit).await;
+
+    if let Err(e) = localize_products(result.items.iter_mut().flat_map(ProductView::products_mut), &session, &currency, &app_state).await {
+        return currency_error(e);
+    }
+
+    HttpResponse::Ok().json(json!(result))
+}
+
+#[derive(Deserialize)]
+pub struct SearchQuery {
+    pub q: String,
+    pub page: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+#[get("/search")]
+pub async fn search_products(
+    session: Session,
+    filter: web::Query<ProductFilter>,
+    query: web::Query<SearchQuery>,
+    currency: web::Query<CurrencyQuery>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let q = query.q.trim();
+
+    if q.is_empty() {
+        return HttpResponse::BadRequest().json(json!({
+            "message": "Search query cannot be empty",
+            "errorCode": "EMPTY_SEARCH_QUERY"
+        }));
+    }
+
+    let page = query.page.unwrap_or(1);
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+
+    let filter = match with_category(filter.into_inner(), &app_state).await {
+        Ok(filter) => filter,
+        Err(response) => return response,
+    };
+
+    let filter = match with_price_currency(filter, &session, &currency, &app_state).await {
+        Ok(filter) => filter,
+        Err(e) => return currency_error(e),
+    };
+
+    let mut result = app_state.products_store.search_products(q, &filter, page, limit).await;
+    let products = result.items.iter_mut().flat_map(|item| item.product.products_mut());
+
+    if let Err(e) = localize_products(products, &session, &currency, &app_state).await {
+        return currency_error(e);
+    }
+
+    HttpResponse::Ok().json(json!(result))
+}
+
+#[derive(Deserialize)]
+pub struct SuggestQuery {
+    pub q: String,
+    pub limit: Option<usize>,
+}
+
+#[get("/suggest")]
+pub async fn suggest_products(
+    query: web::Query<SuggestQuery>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let limit = query.limit.unwrap_or(DEFAULT_SUGGESTION_LIMIT).min(MAX_SUGGESTION_LIMIT);
+
+    let result = app_state.products_store.suggest(query.q.trim(), limit).await;
+    HttpResponse::Ok().json(json!(result))
+}
+
+#[derive(Deserialize)]
+pub struct FacetsQuery {
+    pub q: Option<String>,
+}
+
+#[get("/facets")]
+pub async fn get_product_facets(
+    session: Session,
+    filter: web::Query<ProductFilter>,
+    query: web::Query<FacetsQuery>,
+    currency: web::Query<CurrencyQuery>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let q = query.q.as_deref().map(str::trim).filter(|q| !q.is_empty());
+
+    let filter = match with_category(filter.into_inner(), &app_state).await {
+        Ok(filter) => filter,
+        Err(response) => return response,
+    };
+
+    let filter = match with_price_currency(filter, &session, &currency, &app_state).await {
+        Ok(filter) => filter,
+        Err(e) => return currency_error(e),
+    };
+
+    let result = app_state.products_store.facets(q, &filter).await;
+    HttpResponse::Ok().json(json!(result))
+}
+
+#[get("/article/{article}")]
+pub async fn get_product_by_article(
+    req: HttpRequest,
+    session: Session,
+    path: web::Path<String>,
+    currency: web::Query<CurrencyQuery>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let article = path.into_inner();
+    let product = app_state.products_store.find_by_article(&article).await;
+    product_response(product, &req, &session, &currency, &app_state).await
+}
+
+#[get("/pathurl/{pathurl:.*}")]
+pub async fn get_product_by_pathurl(
+    req: HttpRequest,
+    session: Session,
+    path: web::Path<String>,
+    currency: web::Query<CurrencyQuery>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let pathurl = path.into_inner();
+    let product = app_state.products_store.find_by_pathurl(&pathurl).await;
+    product_response(product, &req, &session, &currency, &app_state).await
+}
+
+#[get("/{product_id}")]
+pub async fn get_product(
+    req: HttpRequest,
+    session: Session,
+    path: web::Path<Uuid>,
+    currency: web::Query<CurrencyQuery>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let product_id = path.into_inner();
+    let product = app_state.products_store.get_product_view(product_id).await;
+    product_response(product, &req, &session, &currency, &app_state).await
+}
+
+async fn product_response(
+    product: Option<ProductView>,
+    req: &HttpRequest,
+    session: &Session,
+    currency: &CurrencyQuery,
+    app_state: &AppState
+) -> HttpResponse {
+    match product {
+        Some(mut product) => {
+            if !is_crawler(req) {
+                let visitor = current_visitor(session);
+                let viewed = product.selected_variant.unwrap_or(product.product.uuid);
+                app_state.recently_viewed_store.record_view(visitor.id, visitor.guest, viewed).await;
+            }
+
+            product.breadcrumbs = app_state.categories_store.breadcrumbs(&product.product.tags).await;
+
+            match localize_products(product.products_mut(), session, currency, app_state).await {
+                Ok(()) => HttpResponse::Ok().json(json!(product)),
+                Err(e) => currency_error(e),
+            }
+        }
+        None => HttpResponse::NotFound().json(json!({
+            "message": "Product not found",
+            "errorCode": "PRODUCT_NOT_FOUND"
+        })),
+    }
+}
+
+async fn with_category(mut filter: ProductFilter, app_state: &AppState) -> Result<ProductFilter, HttpResponse> {
+    if let Some(category) = filter.category.as_deref().filter(|c| !c.trim().is_empty()) {
+        let tags = app_state.categories_store.category_tags(category).await.map_err(|e| {
+            HttpResponse::NotFound().json(json!({
+                "message": e.message,
+                "errorCode": e.error_code
+            }))
+        })?;
+
+        filter.category_tags = Some(tags);
+    }
+
+    Ok(filter)
+}
+
+async fn with_price_currency(
+    mut filter: ProductFilter,
+    session: &Session,
+    currency: &CurrencyQuery,
+    app_state: &AppState,
+) -> Result<ProductFilter, CustomError> {
+    if let Some(currency) = display_currency(session, currency, app_state).await? {
+        filter.price_currency = Some((currency, app_state.exchange_rates_store.get_rates().await));
+    }
+
+    Ok(filter)
+}
+
+fn currency_error(e: CustomError) -> HttpResponse {
+    HttpResponse::BadRequest().json(json!({
+        "message": e.message,
+        "errorCode": e.error_code
+    }))
+}
@@ -0,0 +1,70 @@
+use actix_session::Session;
+use actix_web::{delete, get, web, HttpResponse, Responder};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::state::app_state::AppState;
+use crate::utils::func::{existing_visitor, localize_products, CurrencyQuery};
+use crate::utils::product_store::ProductView;
+
+#[derive(Deserialize)]
+pub struct RecentlyViewedQuery {
+    pub limit: Option<usize>,
+}
+
+#[get("/")]
+pub async fn get_recently_viewed(
+    session: Session,
+    query: web::Query<RecentlyViewedQuery>,
+    currency: web::Query<CurrencyQuery>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let limit = query.limit.unwrap_or(app_state.recently_viewed_store.limit);
+
+    let viewed = match existing_visitor(&session) {
+        Some(visitor) => app_state.recently_viewed_store.get_recently_viewed(visitor.id).await,
+        None => Vec::new(),
+    };
+
+    let product_ids: Vec<Uuid> = viewed
+        .into_iter()
+        .map(|item| item.product_id)
+        .take(limit)
+        .collect();
+
+    let mut products = app_state.products_store.product_views(&product_ids, &HashSet::new()).await;
+
+    match localize_products(products.iter_mut().flat_map(ProductView::products_mut), &session, &currency, &app_state).await {
+        Ok(()) => HttpResponse::Ok().json(json!(products)),
+        Err(e) => HttpResponse::BadRequest().json(json!({
+            "message": e.message,
+            "errorCode": e.error_code
+        })),
+    }
+}
+
+#[delete("/")]
+pub async fn clear_recently_viewed(
+    session: Session,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let Some(visitor) = existing_visitor(&session) else {
+        return HttpResponse::Ok().json(json!({
+            "message": "Recently viewed products cleared",
+            "errorCode": "SUCCESS"
+        }));
+    };
+
+    match app_state.recently_viewed_store.clear(visitor.id).await {
+        Ok(_) => HttpResponse::Ok().json(json!({
+            "message": "Recently viewed products cleared",
+            "errorCode": "SUCCESS"
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(json!({
+            "message": e.to_string(),
+            "errorCode": "SAVE_ERROR"
+        })),
+    }
+}
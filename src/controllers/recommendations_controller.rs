@@ -0,0 +1,80 @@
+use actix_session::Session;
+use actix_web::{get, web, HttpResponse, Responder};
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::state::app_state::AppState;
+use crate::utils::func::{localize_products, CurrencyQuery};
+use crate::utils::product_store::ProductView;
+
+const DEFAULT_RECOMMENDATION_LIMIT: usize = 6;
+const MAX_RECOMMENDATION_LIMIT: usize = 24;
+
+#[derive(Deserialize)]
+pub struct RecommendationQuery {
+    pub limit: Option<usize>,
+}
+
+impl RecommendationQuery {
+    fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_RECOMMENDATION_LIMIT).clamp(1, MAX_RECOMMENDATION_LIMIT)
+    }
+}
+
+#[get("/{product_id}/recommendations")]
+pub async fn get_product_recommendations(
+    session: Session,
+    path: web::Path<Uuid>,
+    query: web::Query<RecommendationQuery>,
+    currency: web::Query<CurrencyQuery>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let Some(product) = app_state.products_store.get_product(path.into_inner()).await else {
+        return HttpResponse::NotFound().json(json!({
+            "message": "Product not found",
+            "errorCode": "PRODUCT_NOT_FOUND"
+        }));
+    };
+
+    let product_id = product.parent.unwrap_or(product.uuid);
+    let mut result = app_state.orders_store.recommender.for_product(product_id, &app_state, query.limit()).await;
+
+    let products = result.frequently_bought_together
+        .iter_mut()
+        .chain(result.similar.iter_mut())
+        .flat_map(ProductView::products_mut);
+
+    match localize_products(products, &session, &currency, &app_state).await {
+        Ok(()) => HttpResponse::Ok().json(json!(result)),
+        Err(e) => HttpResponse::BadRequest().json(json!({
+            "message": e.message,
+            "errorCode": e.error_code
+        })),
+    }
+}
+
+#[get("/for-you")]
+pub async fn get_recommendations_for_you(
+    session: Session,
+    query: web::Query<RecommendationQuery>,
+    currency: web::Query<CurrencyQuery>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let Some(user_id) = session.get::<Uuid>("user_id").unwrap_or(None) else {
+        return HttpResponse::Unauthorized().json(json!({
+            "message": "Unauthorized",
+            "errorCode": "UNAUTHORIZED_ACCESS"
+        }));
+    };
+
+    let mut result = app_state.orders_store.recommender.for_user(user_id, &app_state, query.limit()).await;
+
+    match localize_products(result.iter_mut().flat_map(ProductView::products_mut), &session, &currency, &app_state).await {
+        Ok(()) => HttpResponse::Ok().json(json!(result)),
+        Err(e) => HttpResponse::BadRequest().json(json!({
+            "message": e.message,
+            "errorCode": e.error_code
+        })),
+    }
+}
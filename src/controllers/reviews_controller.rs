@@ -0,0 +1,129 @@
+use actix_session::Session;
+use actix_web::{get, post, web, HttpResponse, Responder};
+use serde::Deserialize;
+use serde_json::json;
+use std::error::Error as StdError;
+use uuid::Uuid;
+
+use crate::state::app_state::AppState;
+use crate::utils::error::CustomError;
+use crate::utils::reviews_store::ReviewRequest;
+
+const DEFAULT_PAGE_LIMIT: usize = 10;
+const MAX_PAGE_LIMIT: usize = 50;
+
+#[derive(Deserialize)]
+pub struct ReportRequest {
+    pub reason: String,
+}
+
+#[derive(Deserialize)]
+pub struct ReviewsPageQuery {
+    pub page: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+#[get("/{product_id}/reviews")]
+pub async fn get_product_reviews(
+    path: web::Path<Uuid>,
+    query: web::Query<ReviewsPageQuery>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let product_id = path.into_inner();
+
+    if app_state.products_store.get_product(product_id).await.is_none() {
+        return HttpResponse::NotFound().json(json!({
+            "message": "Product not found",
+            "errorCode": "PRODUCT_NOT_FOUND"
+        }));
+    }
+
+    let page = query.page.unwrap_or(1);
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT);
+
+    let result = app_state.reviews_store.get_reviews(product_id, page, limit).await;
+    HttpResponse::Ok().json(json!(result))
+}
+
+#[post("/{product_id}/reviews")]
+pub async fn add_product_review(
+    session: Session,
+    path: web::Path<Uuid>,
+    data: web::Json<ReviewRequest>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let Some(user_id) = session.get::<Uuid>("user_id").unwrap_or(None) else {
+        return HttpResponse::Unauthorized().json(json!({
+            "message": "Unauthorized",
+            "errorCode": "UNAUTHORIZED_ACCESS"
+        }));
+    };
+
+    let Some(user) = app_state.users_store.find_user_by_id(user_id).await else {
+        return HttpResponse::NotFound().json(json!({
+            "message": "User not found",
+            "errorCode": "USER_NOT_FOUND"
+        }));
+    };
+
+    let product_id = path.into_inner();
+
+    match app_state.reviews_store.add_review(user_id, &user.username, product_id, data.into_inner()).await {
+        Ok(review) => HttpResponse::Created().json(json!({
+            "message": "Review submitted for moderation",
+            "errorCode": "SUCCESS",
+            "review": review
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+#[post("/{product_id}/reviews/{review_id}/report")]
+pub async fn report_review(
+    session: Session,
+    path: web::Path<(Uuid, Uuid)>,
+    data: web::Json<ReportRequest>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let Some(user_id) = session.get::<Uuid>("user_id").unwrap_or(None) else {
+        return HttpResponse::Unauthorized().json(json!({
+            "message": "Unauthorized",
+            "errorCode": "UNAUTHORIZED_ACCESS"
+        }));
+    };
+
+    let (product_id, review_id) = path.into_inner();
+
+    match app_state.reviews_store.report_review(user_id, product_id, review_id, &data.reason).await {
+        Ok(()) => HttpResponse::Ok().json(json!({
+            "message": "Review reported successfully",
+            "errorCode": "SUCCESS"
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+pub fn error_response(e: Box<dyn StdError>) -> HttpResponse {
+    match e.downcast_ref::<CustomError>() {
+        Some(error) => {
+            let mut response = match error.error_code.as_str() {
+                "PRODUCT_NOT_FOUND" | "REVIEW_NOT_FOUND" => HttpResponse::NotFound(),
+                "REVIEW_NOT_ALLOWED" => HttpResponse::Forbidden(),
+                "REVIEW_ALREADY_EXISTS" | "REVIEW_ALREADY_REPORTED" => HttpResponse::Conflict(),
+                _ => HttpResponse::BadRequest(),
+            };
+
+            response.json(json!({
+                "message": error.message,
+                "errorCode": error.error_code
+            }))
+        }
+        None => {
+            log::error!("Failed to save review: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "message": "Failed to save review",
+                "errorCode": "SAVE_ERROR"
+            }))
+        }
+    }
+}
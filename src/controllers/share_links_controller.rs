@@ -0,0 +1,164 @@
+use actix_session::Session;
+use actix_web::{delete, get, post, web, HttpResponse, Responder};
+use serde_json::json;
+use std::error::Error as StdError;
+use uuid::Uuid;
+
+use crate::state::app_state::AppState;
+use crate::utils::cart_store::ProductWithCount;
+use crate::utils::error::CustomError;
+use crate::utils::func::{current_visitor, localize_products, CurrencyQuery};
+use crate::utils::share_links_store::{ShareKind, ShareLink, ShareLinkRequest};
+
+fn unauthorized() -> HttpResponse {
+    HttpResponse::Unauthorized().json(json!({
+        "message": "Unauthorized",
+        "errorCode": "UNAUTHORIZED_ACCESS"
+    }))
+}
+
+async fn shared_items(link: &ShareLink, app_state: &AppState) -> Vec<ProductWithCount> {
+    match link.kind {
+        ShareKind::Cart => app_state.carts_store.validate_cart(link.user_id, None).await.items,
+        ShareKind::Favorites => {
+            let product_ids: Vec<Uuid> = app_state
+                .favorites_store
+                .get_favorites(link.user_id)
+                .await
+                .iter()
+                .map(|p| p.uuid)
+                .collect();
+
+            app_state
+                .products_store
+                .resolved_products(&product_ids)
+                .await
+                .into_iter()
+                .map(|product| ProductWithCount { product, count: 1 })
+                .collect()
+        }
+    }
+}
+
+#[post("/")]
+pub async fn create_share_link(
+    session: Session,
+    data: web::Json<ShareLinkRequest>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let Some(user_id) = session.get::<Uuid>("user_id").unwrap_or(None) else {
+        return unauthorized();
+    };
+
+    match app_state.share_links_store.create(user_id, &data).await {
+        Ok(link) => HttpResponse::Ok().json(link),
+        Err(e) => error_response(e),
+    }
+}
+
+#[get("/")]
+pub async fn get_share_links(
+    session: Session,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let Some(user_id) = session.get::<Uuid>("user_id").unwrap_or(None) else {
+        return unauthorized();
+    };
+
+    HttpResponse::Ok().json(app_state.share_links_store.get_user_links(user_id).await)
+}
+
+#[delete("/{link_id}")]
+pub async fn revoke_share_link(
+    session: Session,
+    path: web::Path<Uuid>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let Some(user_id) = session.get::<Uuid>("user_id").unwrap_or(None) else {
+        return unauthorized();
+    };
+
+    match app_state.share_links_store.revoke(user_id, path.into_inner()).await {
+        Ok(()) => HttpResponse::Ok().json(json!({
+            "message": "Share link revoked",
+            "errorCode": "SUCCESS"
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+#[get("/{token}")]
+pub async fn get_shared_items(
+    session: Session,
+    path: web::Path<String>,
+    currency: web::Query<CurrencyQuery>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let link = match app_state.share_links_store.resolve(&path).await {
+        Ok(link) => link,
+        Err(e) => return error_response(Box::new(e)),
+    };
+
+    let mut items = shared_items(&link, &app_state).await;
+
+    if let Err(e) = localize_products(items.iter_mut().map(|item| &mut item.product), &session, &currency, &app_state).await {
+        return HttpResponse::BadRequest().json(json!({
+            "message": e.message,
+            "errorCode": e.error_code
+        }));
+    }
+
+    HttpResponse::Ok().json(json!({
+        "kind": link.kind,
+        "expires_at": link.expires_at,
+        "items": items
+    }))
+}
+
+#[post("/{token}/copy")]
+pub async fn copy_shared_items(
+    session: Session,
+    path: web::Path<String>,
+    app_state: web::Data<AppState>
+) -> impl Responder {
+    let link = match app_state.share_links_store.resolve(&path).await {
+        Ok(link) => link,
+        Err(e) => return error_response(Box::new(e)),
+    };
+
+    let items = shared_items(&link, &app_state).await;
+    let visitor = current_visitor(&session);
+
+    match app_state.carts_store.copy_items_to_cart(visitor.id, visitor.guest, items).await {
+        Ok(skipped) => HttpResponse::Ok().json(json!({
+            "message": "Shared items copied to cart",
+            "errorCode": "SUCCESS",
+            "skipped": skipped
+        })),
+        Err(e) => error_response(e),
+    }
+}
+
+fn error_response(e: Box<dyn StdError>) -> HttpResponse {
+    match e.downcast_ref::<CustomError>() {
+        Some(error) => {
+            let mut response = match error.error_code.as_str() {
+                "INVALID_SHARE_TOKEN" | "SHARE_LINK_NOT_FOUND" => HttpResponse::NotFound(),
+                "SHARE_LINK_EXPIRED" => HttpResponse::Gone(),
+                _ => HttpResponse::BadRequest(),
+            };
+
+            response.json(json!({
+                "message": error.message,
+                "errorCode": error.error_code
+            }))
+        }
+        None => {
+            log::error!("Failed to save share link: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "message": "Failed to save share link",
+                "errorCode": "SAVE_ERROR"
+            }))
+        }
+    }
+}
@@ -9,12 +9,6 @@ use crate::models::user::{CreditCard, FullProfileUpdate, PartialProfileUpdate};
 use crate::state::app_state::AppState;
 use crate::utils::func::mask_card_number;
 
-#[derive(Serialize, Deserialize)]
-pub struct MyData {
-    pub name: String,
-    pub age: u8,
-}
-
 #[derive(Serialize, Deserialize)]
 pub struct CreditCardInput {
     pub cardholder_name: String,
@@ -34,7 +28,7 @@ pub async fn profile(session: Session, app_state: web::Data<AppState>) -> impl R
                 .unwrap_or(&Vec::new())
                 .iter()
                 .map(|cc| CreditCard {
-                    id: cc.id.clone(),
+                    id: cc.id,
                     cardholder_name: cc.cardholder_name.clone(),
                     card_number: mask_card_number(&cc.card_number),
                     expiration_date: cc.expiration_date.clone(),
@@ -59,6 +53,7 @@ pub async fn profile(session: Session, app_state: web::Data<AppState>) -> impl R
                 "address": user.address,
                 "zip_code": user.zip_code,
                 "credit_cards": masked_credit_cards,
+                "preferred_currency": user.preferred_currency,
             }));
         }
     }
@@ -93,7 +88,7 @@ pub async fn update_profile_full(
             user.email = payload.email.clone();
             user.phone_number = Some(payload.phone_number.clone());
             user.date_of_birth = Some(payload.date_of_birth.clone());
-            user.avatar_url = Some(payload.avatar_url.clone().unwrap_or_else(|| "".to_string()));
+            user.avatar_url = Some(payload.avatar_url.clone().unwrap_or_default());
             user.country = Some(payload.country.clone());
             user.region = Some(payload.region.clone());
             user.city = Some(payload.city.clone());
@@ -104,6 +99,18 @@ pub async fn update_profile_full(
                 user.credit_cards = Some(credit_cards.clone());
             }
 
+            if let Some(preferred_currency) = &payload.preferred_currency {
+                match app_state.exchange_rates_store.resolve_currency(Some(preferred_currency), None).await {
+                    Ok(currency) => user.preferred_currency = currency,
+                    Err(e) => {
+                        return HttpResponse::BadRequest().json(json!({
+                            "message": e.message,
+                            "errorCode": e.error_code
+                        }));
+                    }
+                }
+            }
+
             if app_state.users_store.update_user(user).await {
                 return HttpResponse::Ok().json(json!({
                     "message": "Profile updated successfully",
@@ -188,6 +195,17 @@ pub async fn update_profile_partial(
             if let Some(credit_cards) = &payload.credit_cards {
                 user.credit_cards = Some(credit_cards.clone());
             }
+            if let Some(preferred_currency) = &payload.preferred_currency {
+                match app_state.exchange_rates_store.resolve_currency(Some(preferred_currency), None).await {
+                    Ok(currency) => user.preferred_currency = currency,
+                    Err(e) => {
+                        return HttpResponse::BadRequest().json(json!({
+                            "message": e.message,
+                            "errorCode": e.error_code
+                        }));
+                    }
+                }
+            }
 
             if app_state.users_store.update_user(user).await {
                 return HttpResponse::Ok().json(json!({
@@ -1,3 +1,4 @@
+mod cli;
 mod config;
 mod controllers;
 mod models;
@@ -16,12 +17,22 @@ use std::path::Path;
 use tokio::fs;
 
 use crate::utils::user_store::UserStore;
-use crate::utils::cart_store::CartStore;
+use crate::utils::cart_store::{spawn_cart_pruner, CartExpiry, CartStore};
 use crate::utils::favorites_store::FavoritesStore;
 use crate::utils::orders_store::OrdersStore;
 use crate::utils::logger::init_logger;
+use crate::utils::file_watcher::spawn_data_watcher;
 use crate::state::app_state::AppState;
 use crate::utils::promo_codes_store::PromoCodesStore;
+use crate::utils::product_store::ProductStore;
+use crate::utils::exchange_rates_store::ExchangeRatesStore;
+use crate::utils::reviews_store::ReviewsStore;
+use crate::utils::banned_words_store::BannedWordsStore;
+use crate::utils::categories_store::CategoriesStore;
+use crate::utils::recommender::Recommender;
+use crate::utils::recently_viewed_store::{spawn_recently_viewed_flusher, RecentlyViewedStore};
+use crate::utils::comparisons_store::ComparisonsStore;
+use crate::utils::share_links_store::ShareLinksStore;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
@@ -31,6 +42,15 @@ async fn main() -> std::io::Result<()> {
 
     let config = config::Config::from_env().expect("Failed to load configuration");
 
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().is_some_and(|arg| arg == "catalog") {
+        if let Err(e) = cli::run_catalog_command(&args[1..], &config).await {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     info!("🚀 サーバーが {} で起動しています...", config.server_address);
 
     ensure_static_directory_exists().await;
@@ -39,15 +59,19 @@ async fn main() -> std::io::Result<()> {
         .await
         .expect("Failed to initialize UserStore"));
     
-    let carts_store = Arc::new(CartStore::new(config.carts_file_path.clone(), config.products_file_path.clone())
+    let products_store = Arc::new(ProductStore::new(config.products_file_path.clone(), config.low_stock_threshold)
+        .await
+        .expect("Failed to initialize ProductStore"));
+
+    let carts_store = Arc::new(CartStore::new(config.carts_file_path.clone(), products_store.clone())
         .await
         .expect("Failed to initialize CartStore"));
     
-    let favorites_store = Arc::new(FavoritesStore::new(config.favorites_file_path.clone(), config.products_file_path.clone())
+    let favorites_store = Arc::new(FavoritesStore::new(config.favorites_file_path.clone(), products_store.clone())
         .await
         .expect("Failed to initialize FavoritesStore"));
     
-    let orders_store = Arc::new(OrdersStore::new(config.orders_file_path.clone())
+    let orders_store = Arc::new(OrdersStore::new(config.orders_file_path.clone(), products_store.clone(), Arc::new(Recommender::default()))
         .await
         .expect("Failed to initialize OrdersStore"));
 
@@ -55,14 +79,74 @@ async fn main() -> std::io::Result<()> {
         .await
         .expect("Failed to initialize PromoCodesStore"));
 
+    let exchange_rates_store = Arc::new(ExchangeRatesStore::new(config.exchange_rates_file_path.clone())
+        .await
+        .expect("Failed to initialize ExchangeRatesStore"));
+
+    let banned_words_store = Arc::new(BannedWordsStore::new(config.banned_words_file_path.clone())
+        .await
+        .expect("Failed to initialize BannedWordsStore"));
+
+    let reviews_store = Arc::new(ReviewsStore::new(
+        config.reviews_file_path.clone(),
+        products_store.clone(),
+        orders_store.clone(),
+        banned_words_store.clone(),
+    )
+        .await
+        .expect("Failed to initialize ReviewsStore"));
+
+    reviews_store.sync_product_ratings()
+        .await
+        .expect("Failed to sync product ratings with reviews");
+
+    let categories_store = Arc::new(CategoriesStore::new(config.categories_file_path.clone())
+        .await
+        .expect("Failed to initialize CategoriesStore"));
+
+    let recently_viewed_store = Arc::new(RecentlyViewedStore::new(config.recently_viewed_file_path.clone(), config.recently_viewed_limit)
+        .await
+        .expect("Failed to initialize RecentlyViewedStore"));
+
+    let comparisons_store = Arc::new(ComparisonsStore::new(config.comparisons_file_path.clone(), products_store.clone(), config.comparison_limit)
+        .await
+        .expect("Failed to initialize ComparisonsStore"));
+
+    let share_links_store = Arc::new(ShareLinksStore::new(config.share_links_file_path.clone(), config.share_link_secret.clone(), config.share_link_expiry_days)
+        .await
+        .expect("Failed to initialize ShareLinksStore"));
+
     let app_state = web::Data::new(AppState::new(
         users_store,
         orders_store,
         favorites_store,
         carts_store,
         promocodes_store,
+        products_store,
+        exchange_rates_store,
+        reviews_store,
+        banned_words_store,
+        categories_store,
+        recently_viewed_store,
+        comparisons_store,
+        share_links_store,
     ));
 
+    if config.data_watch_interval_secs > 0 {
+        spawn_data_watcher(app_state.clone(), std::time::Duration::from_secs(config.data_watch_interval_secs));
+    }
+
+    if config.cart_prune_interval_secs > 0 {
+        let expiry = CartExpiry { user_days: config.cart_expiry_days, guest_days: config.guest_cart_expiry_days };
+        spawn_cart_pruner(app_state.carts_store.clone(), expiry, std::time::Duration::from_secs(config.cart_prune_interval_secs));
+    }
+
+    spawn_recently_viewed_flusher(
+        app_state.recently_viewed_store.clone(),
+        std::time::Duration::from_secs(config.recently_viewed_flush_interval_secs),
+    );
+    let recently_viewed_store = app_state.recently_viewed_store.clone();
+
     let server_address_clone = config.server_address.clone();
 
     actix_web::rt::spawn(async move {
@@ -93,7 +177,13 @@ async fn main() -> std::io::Result<()> {
     })
     .bind(&config.server_address)?
     .run()
-    .await
+    .await?;
+
+    if let Err(e) = recently_viewed_store.flush().await {
+        log::error!("Failed to save recently viewed products: {}", e);
+    }
+
+    Ok(())
 }
 
 async fn ensure_static_directory_exists() {
@@ -1,2 +1,3 @@
 pub mod user;
-pub mod product;
\ No newline at end of file
+pub mod product;
+pub mod money;
\ No newline at end of file
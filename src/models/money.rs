@@ -0,0 +1,258 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+use crate::utils::error::CustomError;
+
+pub const DEFAULT_CURRENCY: &str = "RUB";
+const MINOR_UNITS: i64 = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Money {
+    pub amount: i64,
+    pub currency: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum LegacyAmount {
+    Text(String),
+    Integer(i64),
+    Float(f64),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum MoneyValue {
+    Structured { amount: LegacyAmount, currency: String },
+    Legacy(LegacyAmount),
+}
+
+impl Money {
+    pub fn new(amount: i64, currency: &str) -> Self {
+        Money {
+            amount,
+            currency: currency.to_uppercase(),
+        }
+    }
+
+    pub fn zero(currency: &str) -> Self {
+        Money::new(0, currency)
+    }
+
+    pub fn from_major(value: f64, currency: &str) -> Self {
+        Money::new((value * MINOR_UNITS as f64).round() as i64, currency)
+    }
+
+    pub fn to_major(&self) -> f64 {
+        self.amount as f64 / MINOR_UNITS as f64
+    }
+
+    pub fn parse(value: &str, currency: &str) -> Result<Self, CustomError> {
+        let invalid = || CustomError::new(&format!("Invalid price `{}`", value), "INVALID_PRICE");
+
+        let cleaned: String = value
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '\u{a0}' && *c != '\'')
+            .collect();
+
+        let (negative, digits) = match cleaned.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, cleaned.as_str()),
+        };
+
+        if !digits.chars().any(|c| c.is_ascii_digit()) || !digits.chars().all(|c| c.is_ascii_digit() || c == ',' || c == '.') {
+            return Err(invalid());
+        }
+
+        let decimal_separator = match (digits.rfind(','), digits.rfind('.')) {
+            (Some(comma), Some(dot)) => Some(comma.max(dot)),
+            (Some(pos), None) | (None, Some(pos)) => {
+                let separator = &digits[pos..pos + 1];
+                let occurrences = digits.matches(separator).count();
+                let fraction_len = digits.len() - pos - 1;
+
+                if occurrences > 1 || fraction_len == 3 {
+                    None
+                } else {
+                    Some(pos)
+                }
+            }
+            (None, None) => None,
+        };
+
+        let (whole, fraction) = match decimal_separator {
+            Some(pos) => (&digits[..pos], &digits[pos + 1..]),
+            None => (digits, ""),
+        };
+
+        let whole: String = whole.chars().filter(char::is_ascii_digit).collect();
+        if fraction.len() > 2 || !fraction.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+
+        let whole: i64 = if whole.is_empty() { 0 } else { whole.parse().map_err(|_| invalid())? };
+        let fraction: i64 = format!("{:0<2}", fraction).parse().map_err(|_| invalid())?;
+
+        let amount = whole
+            .checked_mul(MINOR_UNITS)
+            .and_then(|a| a.checked_add(fraction))
+            .ok_or_else(invalid)?;
+
+        Ok(Money::new(if negative { -amount } else { amount }, currency))
+    }
+
+    pub fn percentage(&self, percent: f64) -> Money {
+        let basis_points = (percent * 100.0).round() as i128;
+        let scaled = self.amount as i128 * basis_points;
+        let rounded = if scaled >= 0 {
+            (scaled + 5_000) / 10_000
+        } else {
+            (scaled - 5_000) / 10_000
+        };
+
+        Money::new(rounded as i64, &self.currency)
+    }
+
+    pub fn multiply(&self, count: u32) -> Result<Money, CustomError> {
+        let amount = self.amount.checked_mul(count as i64).ok_or_else(overflow)?;
+        Ok(Money::new(amount, &self.currency))
+    }
+
+    pub fn checked_add(&self, other: &Money) -> Result<Money, CustomError> {
+        self.ensure_same_currency(other)?;
+        let amount = self.amount.checked_add(other.amount).ok_or_else(overflow)?;
+        Ok(Money::new(amount, &self.currency))
+    }
+
+    pub fn checked_sub(&self, other: &Money) -> Result<Money, CustomError> {
+        self.ensure_same_currency(other)?;
+        let amount = self.amount.checked_sub(other.amount).ok_or_else(overflow)?;
+        Ok(Money::new(amount, &self.currency))
+    }
+
+    pub fn non_negative(self) -> Money {
+        if self.amount < 0 {
+            Money::zero(&self.currency)
+        } else {
+            self
+        }
+    }
+
+    fn ensure_same_currency(&self, other: &Money) -> Result<(), CustomError> {
+        if self.currency == other.currency {
+            Ok(())
+        } else {
+            Err(CustomError::new(
+                &format!("Cannot combine amounts in {} and {}", self.currency, other.currency),
+                "CURRENCY_MISMATCH",
+            ))
+        }
+    }
+
+    pub fn amount_string(&self) -> String {
+        let sign = if self.amount < 0 { "-" } else { "" };
+        let abs = self.amount.unsigned_abs();
+        format!("{}{}.{:02}", sign, abs / MINOR_UNITS as u64, abs % MINOR_UNITS as u64)
+    }
+}
+
+fn overflow() -> CustomError {
+    CustomError::new("Amount is too large", "AMOUNT_OVERFLOW")
+}
+
+impl LegacyAmount {
+    pub fn into_money(self, currency: &str) -> Result<Money, CustomError> {
+        match self {
+            LegacyAmount::Text(text) => Money::parse(&text, currency),
+            LegacyAmount::Integer(value) => {
+                let amount = value.checked_mul(MINOR_UNITS).ok_or_else(overflow)?;
+                Ok(Money::new(amount, currency))
+            }
+            LegacyAmount::Float(value) => Ok(Money::from_major(value, currency)),
+        }
+    }
+}
+
+impl MoneyValue {
+    pub fn into_money(self, default_currency: &str) -> Result<Money, CustomError> {
+        match self {
+            MoneyValue::Structured { amount, currency } => amount.into_money(&currency),
+            MoneyValue::Legacy(amount) => amount.into_money(default_currency),
+        }
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.amount_string(), self.currency)
+    }
+}
+
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct Repr<'a> {
+            amount: String,
+            currency: &'a str,
+        }
+
+        Repr {
+            amount: self.amount_string(),
+            currency: &self.currency,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        MoneyValue::deserialize(deserializer)?
+            .into_money(DEFAULT_CURRENCY)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(value: &str) -> Result<i64, CustomError> {
+        Money::parse(value, "RUB").map(|money| money.amount)
+    }
+
+    #[test]
+    fn parses_thousands_and_decimal_separators() {
+        assert_eq!(parse("53,400").unwrap(), 5_340_000);
+        assert_eq!(parse("53.40").unwrap(), 5_340);
+        assert_eq!(parse("1.234").unwrap(), 123_400);
+        assert_eq!(parse("1 234,56").unwrap(), 123_456);
+        assert_eq!(parse("1,234,567.8").unwrap(), 123_456_780);
+        assert_eq!(parse("-0,5").unwrap(), -50);
+    }
+
+    #[test]
+    fn rejects_malformed_prices() {
+        for value in [",", ".", "", "-", "12a", "1.2.3,456", "1,2345"] {
+            assert!(parse(value).is_err(), "`{}` should be rejected", value);
+        }
+    }
+
+    #[test]
+    fn percentage_rounds_half_away_from_zero() {
+        assert_eq!(Money::new(999, "RUB").percentage(15.0).amount, 150);
+        assert_eq!(Money::new(1_005, "RUB").percentage(50.0).amount, 503);
+        assert_eq!(Money::new(-1_005, "RUB").percentage(50.0).amount, -503);
+        assert_eq!(Money::new(1_004, "RUB").percentage(12.5).amount, 126);
+        assert_eq!(Money::new(1_000, "RUB").percentage(0.0).amount, 0);
+    }
+
+    #[test]
+    fn arithmetic_reports_overflow_and_currency_mismatch() {
+        let max = Money::new(i64::MAX, "RUB");
+        assert_eq!(max.multiply(2).unwrap_err().error_code, "AMOUNT_OVERFLOW");
+        assert_eq!(max.checked_add(&Money::new(1, "RUB")).unwrap_err().error_code, "AMOUNT_OVERFLOW");
+        assert_eq!(Money::new(i64::MIN, "RUB").checked_sub(&Money::new(1, "RUB")).unwrap_err().error_code, "AMOUNT_OVERFLOW");
+        assert_eq!(Money::new(1, "RUB").checked_add(&Money::new(1, "USD")).unwrap_err().error_code, "CURRENCY_MISMATCH");
+        assert!(LegacyAmount::Integer(i64::MAX).into_money("RUB").is_err());
+    }
+}
@@ -1,20 +1,437 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
+use crate::models::money::{Money, MoneyValue, DEFAULT_CURRENCY};
+use crate::utils::error::CustomError;
+use crate::utils::exchange_rates_store::ExchangeRates;
+
 #[derive(Serialize, Deserialize, Clone)]
+#[serde(try_from = "ProductRecord")]
 pub struct Product {
     pub uuid: Uuid,
     pub pathurl: String,
     pub article: String,
-    pub price: String,
+    pub price: Money,
     pub rating: f64,
     pub reviews: f64,
-    pub currency: String,
     pub discount: Option<f64>,
     pub is_new: bool,
+    pub stock: Option<u32>,
     pub image: String,
     pub name: String,
     pub brand: String,
     pub tags: Vec<String>,
     pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<Uuid>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub options: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub variant_axes: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ProductRecord {
+    uuid: Uuid,
+    pathurl: String,
+    article: String,
+    price: MoneyValue,
+    rating: f64,
+    reviews: f64,
+    currency: Option<String>,
+    discount: Option<f64>,
+    is_new: bool,
+    #[serde(default)]
+    stock: Option<u32>,
+    image: String,
+    name: String,
+    brand: String,
+    tags: Vec<String>,
+    description: Option<String>,
+    #[serde(default)]
+    parent: Option<Uuid>,
+    #[serde(default)]
+    options: BTreeMap<String, String>,
+    #[serde(default)]
+    variant_axes: Vec<String>,
+}
+
+impl TryFrom<ProductRecord> for Product {
+    type Error = CustomError;
+
+    fn try_from(record: ProductRecord) -> Result<Self, Self::Error> {
+        let currency = record.currency.as_deref().unwrap_or(DEFAULT_CURRENCY);
+
+        Ok(Product {
+            uuid: record.uuid,
+            pathurl: record.pathurl,
+            article: record.article,
+            price: record.price.into_money(currency)?,
+            rating: record.rating,
+            reviews: record.reviews,
+            discount: record.discount,
+            is_new: record.is_new,
+            stock: record.stock,
+            image: record.image,
+            name: record.name,
+            brand: record.brand,
+            tags: record.tags,
+            description: record.description,
+            parent: record.parent,
+            options: record.options,
+            variant_axes: record.variant_axes,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ProductDraft {
+    pub uuid: Option<Uuid>,
+    pub pathurl: String,
+    pub article: String,
+    pub price: MoneyValue,
+    pub currency: Option<String>,
+    pub rating: Option<f64>,
+    pub reviews: Option<f64>,
+    pub discount: Option<f64>,
+    pub is_new: Option<bool>,
+    pub stock: Option<u32>,
+    pub image: Option<String>,
+    pub name: String,
+    pub brand: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub description: Option<String>,
+    pub parent: Option<Uuid>,
+    pub options: Option<BTreeMap<String, String>>,
+    pub variant_axes: Option<Vec<String>>,
+}
+
+impl ProductDraft {
+    pub fn into_product(self, uuid: Uuid, existing: Option<&Product>) -> Result<Product, CustomError> {
+        let currency = self.currency
+            .as_deref()
+            .or(existing.map(|p| p.price.currency.as_str()))
+            .unwrap_or(DEFAULT_CURRENCY);
+
+        let product = Product {
+            uuid,
+            pathurl: self.pathurl.trim().to_string(),
+            article: self.article.trim().to_string(),
+            price: self.price.into_money(currency)?,
+            rating: self.rating.or(existing.map(|p| p.rating)).unwrap_or_default(),
+            reviews: self.reviews.or(existing.map(|p| p.reviews)).unwrap_or_default(),
+            discount: self.discount.or(existing.and_then(|p| p.discount)),
+            is_new: self.is_new.or(existing.map(|p| p.is_new)).unwrap_or_default(),
+            stock: self.stock.or(existing.and_then(|p| p.stock)),
+            image: self.image.or(existing.map(|p| p.image.clone())).unwrap_or_default(),
+            name: self.name.trim().to_string(),
+            brand: self.brand
+                .map(|brand| brand.trim().to_string())
+                .or(existing.map(|p| p.brand.clone()))
+                .unwrap_or_default(),
+            tags: self.tags.or(existing.map(|p| p.tags.clone())).unwrap_or_default(),
+            description: self.description.or(existing.and_then(|p| p.description.clone())),
+            parent: self.parent.or(existing.and_then(|p| p.parent)),
+            options: self.options.or(existing.map(|p| p.options.clone())).unwrap_or_default(),
+            variant_axes: self.variant_axes.or(existing.map(|p| p.variant_axes.clone())).unwrap_or_default(),
+        };
+
+        product.validate()?;
+        Ok(product)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ProductPatch {
+    pub pathurl: Option<String>,
+    pub article: Option<String>,
+    pub price: Option<MoneyValue>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub discount: Option<Option<f64>>,
+    pub is_new: Option<bool>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub stock: Option<Option<u32>>,
+    pub image: Option<String>,
+    pub name: Option<String>,
+    pub brand: Option<String>,
+    pub tags: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub description: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub parent: Option<Option<Uuid>>,
+    pub options: Option<BTreeMap<String, String>>,
+    pub variant_axes: Option<Vec<String>>,
+}
+
+/// Distinguishes an explicit `null` (`Some(None)`, clears the field) from an omitted field (`None`).
+fn nullable<'de, T: Deserialize<'de>, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Option<T>>, D::Error> {
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+impl ProductPatch {
+    pub fn apply(self, product: &mut Product) -> Result<(), CustomError> {
+        if let Some(pathurl) = self.pathurl {
+            product.pathurl = pathurl.trim().to_string();
+        }
+        if let Some(article) = self.article {
+            product.article = article.trim().to_string();
+        }
+        if let Some(price) = self.price {
+            product.price = price.into_money(&product.price.currency)?;
+        }
+        if let Some(discount) = self.discount {
+            product.discount = discount;
+        }
+        if let Some(is_new) = self.is_new {
+            product.is_new = is_new;
+        }
+        if let Some(stock) = self.stock {
+            product.stock = stock;
+        }
+        if let Some(image) = self.image {
+            product.image = image;
+        }
+        if let Some(name) = self.name {
+            product.name = name.trim().to_string();
+        }
+        if let Some(brand) = self.brand {
+            product.brand = brand.trim().to_string();
+        }
+        if let Some(tags) = self.tags {
+            product.tags = tags;
+        }
+        if let Some(description) = self.description {
+            product.description = description;
+        }
+        if let Some(parent) = self.parent {
+            product.parent = parent;
+        }
+        if let Some(options) = self.options {
+            product.options = options;
+        }
+        if let Some(variant_axes) = self.variant_axes {
+            product.variant_axes = variant_axes;
+        }
+
+        product.validate()
+    }
+}
+
+impl Product {
+    pub fn validate(&self) -> Result<(), CustomError> {
+        if self.name.is_empty() || self.article.is_empty() || self.pathurl.is_empty() {
+            return Err(CustomError::new(
+                &format!("Product {} must have a name, article and pathurl", self.uuid),
+                "INVALID_PRODUCT",
+            ));
+        }
+
+        if self.price.amount < 0 {
+            return Err(CustomError::new(
+                &format!("Product {} has a negative price `{}`", self.uuid, self.price),
+                "INVALID_PRODUCT_PRICE",
+            ));
+        }
+
+        if self.parent.is_none() && !self.options.is_empty() {
+            return Err(CustomError::new(
+                &format!("Product {} has variant options but no parent", self.uuid),
+                "INVALID_PRODUCT_VARIANT",
+            ));
+        }
+
+        if self.parent.is_some() && !self.variant_axes.is_empty() {
+            return Err(CustomError::new(
+                &format!("Variant {} cannot define its own variant axes", self.uuid),
+                "INVALID_PRODUCT_VARIANT",
+            ));
+        }
+
+        if self.discount.is_some_and(|d| !(0.0..=100.0).contains(&d)) {
+            return Err(CustomError::new(
+                &format!("Product {} discount must be between 0 and 100", self.uuid),
+                "INVALID_PRODUCT_DISCOUNT",
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn discount_amount(&self) -> Money {
+        match self.discount {
+            Some(discount) if discount > 0.0 => self.price.percentage(discount),
+            _ => Money::zero(&self.price.currency),
+        }
+    }
+
+    pub fn final_price(&self) -> Money {
+        Money::new(self.price.amount - self.discount_amount().amount, &self.price.currency)
+    }
+
+    pub fn has_discount(&self) -> bool {
+        self.discount.is_some_and(|d| d > 0.0)
+    }
+
+    pub fn is_variant(&self) -> bool {
+        self.parent.is_some()
+    }
+
+    pub fn ensure_available(&self, count: u32) -> Result<(), CustomError> {
+        match self.stock {
+            Some(stock) if count > stock => Err(CustomError::new(
+                &format!("Not enough stock for {}: requested {}, available {}", self.name, count, stock),
+                "OUT_OF_STOCK",
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProductSort {
+    PriceAsc,
+    PriceDesc,
+    Rating,
+    Reviews,
+    Newest,
+}
+
+#[derive(Deserialize, Clone, Default)]
+pub struct ProductFilter {
+    pub brand: Option<String>,
+    pub tags: Option<String>,
+    pub is_new: Option<bool>,
+    pub has_discount: Option<bool>,
+    pub min_price: Option<f64>,
+    pub max_price: Option<f64>,
+    pub min_rating: Option<f64>,
+    pub category: Option<String>,
+    #[serde(skip)]
+    pub category_tags: Option<Vec<String>>,
+    #[serde(skip)]
+    pub price_currency: Option<(String, ExchangeRates)>,
+}
+
+impl ProductFilter {
+    pub fn brands(&self) -> Vec<String> {
+        split_list(self.brand.as_deref())
+    }
+
+    pub fn tag_list(&self) -> Vec<String> {
+        split_list(self.tags.as_deref())
+    }
+
+    pub fn matches(&self, product: &Product) -> bool {
+        let brands = self.brands();
+        if !brands.is_empty() && !brands.contains(&product.brand.to_lowercase()) {
+            return false;
+        }
+
+        let tags = self.tag_list();
+        if !tags.is_empty() && !product.tags.iter().any(|t| tags.contains(&t.to_lowercase())) {
+            return false;
+        }
+
+        if let Some(category_tags) = &self.category_tags {
+            if !product.tags.iter().any(|t| category_tags.contains(&t.to_lowercase())) {
+                return false;
+            }
+        }
+
+        if let Some(is_new) = self.is_new {
+            if product.is_new != is_new {
+                return false;
+            }
+        }
+
+        if let Some(has_discount) = self.has_discount {
+            if product.has_discount() != has_discount {
+                return false;
+            }
+        }
+
+        if self.min_rating.is_some_and(|min| product.rating < min) {
+            return false;
+        }
+
+        if self.min_price.is_some() || self.max_price.is_some() {
+            let Some(price) = self.filter_price(product) else {
+                return false;
+            };
+
+            if self.min_price.is_some_and(|min| price < min) || self.max_price.is_some_and(|max| price > max) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Final price in the currency the price filters are expressed in, or `None` if it cannot be converted.
+    pub fn filter_price(&self, product: &Product) -> Option<f64> {
+        let price = product.final_price();
+
+        match &self.price_currency {
+            Some((currency, rates)) => rates.convert(&price, currency).ok().map(|p| p.to_major()),
+            None => Some(price.to_major()),
+        }
+    }
+}
+
+fn split_list(value: Option<&str>) -> Vec<String> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .map(|s| s.trim().to_lowercase())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+impl Product {
+    /// A minimal valid product for unit tests; tests set the fields they check.
+    pub fn test_fixture(name: &str, price: &str) -> Product {
+        Product {
+            uuid: Uuid::new_v4(),
+            pathurl: "/product".to_string(),
+            article: "1".to_string(),
+            price: Money::parse(price, DEFAULT_CURRENCY).unwrap(),
+            rating: 0.0,
+            reviews: 0.0,
+            discount: None,
+            is_new: false,
+            stock: None,
+            image: String::new(),
+            name: name.to_string(),
+            brand: String::new(),
+            tags: Vec::new(),
+            description: None,
+            parent: None,
+            options: BTreeMap::new(),
+            variant_axes: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn patch_clears_fields_set_to_null_and_keeps_omitted_ones() {
+        let mut product = Product::test_fixture("Product", "100");
+        product.discount = Some(15.0);
+        product.stock = Some(4);
+        product.description = Some("Description".to_string());
+
+        let patch: ProductPatch = serde_json::from_value(json!({ "discount": null, "description": null })).unwrap();
+        patch.apply(&mut product).unwrap();
+
+        assert_eq!(product.discount, None);
+        assert_eq!(product.description, None);
+        assert_eq!(product.stock, Some(4));
+    }
 }
@@ -29,6 +29,10 @@ pub struct User {
     pub address: Option<String>,
     pub zip_code: Option<String>,
     pub credit_cards: Option<Vec<CreditCard>>,
+    #[serde(default)]
+    pub preferred_currency: Option<String>,
+    #[serde(default)]
+    pub is_admin: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -45,6 +49,7 @@ pub struct FullProfileUpdate {
     pub address: String,
     pub zip_code: String,
     pub credit_cards: Option<Vec<CreditCard>>,
+    pub preferred_currency: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -61,4 +66,5 @@ pub struct PartialProfileUpdate {
     pub address: Option<String>,
     pub zip_code: Option<String>,
     pub credit_cards: Option<Vec<CreditCard>>,
+    pub preferred_currency: Option<String>,
 }
\ No newline at end of file
@@ -0,0 +1,30 @@
+use actix_web::web;
+
+use crate::controllers::admin_products_controller::{create_product, update_product, delete_product, upsert_products, import_products, export_products, get_low_stock_products};
+use crate::controllers::admin_reviews_controller::{get_moderation_queue, approve_review, reject_review};
+
+const MAX_CATALOG_PAYLOAD_SIZE: usize = 16 * 1024 * 1024;
+
+pub fn init_admin_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/admin")
+            .service(
+                web::scope("/products")
+                    .app_data(web::PayloadConfig::new(MAX_CATALOG_PAYLOAD_SIZE))
+                    .app_data(web::JsonConfig::default().limit(MAX_CATALOG_PAYLOAD_SIZE))
+                    .service(upsert_products)
+                    .service(import_products)
+                    .service(export_products)
+                    .service(get_low_stock_products)
+                    .service(create_product)
+                    .service(update_product)
+                    .service(delete_product)
+            )
+            .service(
+                web::scope("/reviews")
+                    .service(get_moderation_queue)
+                    .service(approve_review)
+                    .service(reject_review)
+            )
+    );
+}
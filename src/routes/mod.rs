@@ -6,6 +6,7 @@ pub mod auth_routes;
 pub mod users_routes;
 pub mod app_routes;
 pub mod store_routes;
+pub mod admin_routes;
 
 #[derive(OpenApi)]
 struct ApiDoc;
@@ -20,7 +21,8 @@ pub fn init_routes(cfg: &mut web::ServiceConfig) {
         web::scope("/api")
             .configure(auth_routes::init_auth_routes)
             .configure(users_routes::init_users_routes)
-            .configure(store_routes::init_store_routes),
+            .configure(store_routes::init_store_routes)
+            .configure(admin_routes::init_admin_routes),
     );
 
     app_routes::init_app_routes(cfg);
@@ -1,9 +1,17 @@
 use actix_web::web;
 
-use crate::controllers::carts_controller::{get_cart, add_product_to_cart, remove_product_from_cart};
+use crate::controllers::carts_controller::{get_cart, add_product_to_cart, remove_product_from_cart, set_cart_item_quantity, update_cart, clear_cart, get_cart_summary, acknowledge_cart_changes, get_saved_for_later, save_for_later, move_to_cart, remove_saved_item};
 use crate::controllers::favorites_controller::{get_favorites, add_product_to_favorites, remove_product_from_favorites};
-use crate::controllers::orders_controller::{get_orders, create_order, delete_order};
+use crate::controllers::orders_controller::{get_orders, create_order, delete_order, cancel_order, return_order};
 use crate::controllers::promocodes_controller::{validate_promo_code};
+use crate::controllers::currencies_controller::{get_exchange_rates};
+use crate::controllers::categories_controller::{get_categories, get_category};
+use crate::controllers::recommendations_controller::{get_product_recommendations, get_recommendations_for_you};
+use crate::controllers::recently_viewed_controller::{get_recently_viewed, clear_recently_viewed};
+use crate::controllers::comparisons_controller::{get_comparison, add_product_to_comparison, remove_product_from_comparison, clear_comparison};
+use crate::controllers::share_links_controller::{create_share_link, get_share_links, revoke_share_link, get_shared_items, copy_shared_items};
+use crate::controllers::products_controller::{get_products, search_products, suggest_products, get_product_facets, get_product, get_product_by_article, get_product_by_pathurl};
+use crate::controllers::reviews_controller::{get_product_reviews, add_product_review, report_review};
 
 pub fn init_store_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -11,8 +19,17 @@ pub fn init_store_routes(cfg: &mut web::ServiceConfig) {
             .service(
                 web::scope("/carts")
                     .service(get_cart)
+                    .service(get_cart_summary)
+                    .service(acknowledge_cart_changes)
+                    .service(get_saved_for_later)
+                    .service(save_for_later)
+                    .service(move_to_cart)
+                    .service(remove_saved_item)
                     .service(add_product_to_cart)
                     .service(remove_product_from_cart)
+                    .service(set_cart_item_quantity)
+                    .service(update_cart)
+                    .service(clear_cart)
             )
             .service(
                 web::scope("/favorites")
@@ -25,10 +42,59 @@ pub fn init_store_routes(cfg: &mut web::ServiceConfig) {
                     .service(get_orders)
                     .service(create_order)
                     .service(delete_order)
+                    .service(cancel_order)
+                    .service(return_order)
             )
             .service(
                 web::scope("/promocode")
                     .service(validate_promo_code)
             )
+            .service(
+                web::scope("/products")
+                    .service(get_products)
+                    .service(search_products)
+                    .service(suggest_products)
+                    .service(get_product_facets)
+                    .service(get_product_by_article)
+                    .service(get_product_by_pathurl)
+                    .service(get_product_reviews)
+                    .service(add_product_review)
+                    .service(report_review)
+                    .service(get_product_recommendations)
+                    .service(get_product)
+            )
+            .service(
+                web::scope("/categories")
+                    .service(get_categories)
+                    .service(get_category)
+            )
+            .service(
+                web::scope("/recommendations")
+                    .service(get_recommendations_for_you)
+            )
+            .service(
+                web::scope("/comparison")
+                    .service(get_comparison)
+                    .service(add_product_to_comparison)
+                    .service(clear_comparison)
+                    .service(remove_product_from_comparison)
+            )
+            .service(
+                web::scope("/share")
+                    .service(create_share_link)
+                    .service(get_share_links)
+                    .service(revoke_share_link)
+                    .service(get_shared_items)
+                    .service(copy_shared_items)
+            )
+            .service(
+                web::scope("/recently-viewed")
+                    .service(get_recently_viewed)
+                    .service(clear_recently_viewed)
+            )
+            .service(
+                web::scope("/currencies")
+                    .service(get_exchange_rates)
+            )
     );
 }
\ No newline at end of file
@@ -5,6 +5,14 @@ use crate::utils::orders_store::OrdersStore;
 use crate::utils::favorites_store::FavoritesStore;
 use crate::utils::cart_store::CartStore;
 use crate::utils::promo_codes_store::PromoCodesStore;
+use crate::utils::product_store::ProductStore;
+use crate::utils::exchange_rates_store::ExchangeRatesStore;
+use crate::utils::reviews_store::ReviewsStore;
+use crate::utils::banned_words_store::BannedWordsStore;
+use crate::utils::categories_store::CategoriesStore;
+use crate::utils::recently_viewed_store::RecentlyViewedStore;
+use crate::utils::comparisons_store::ComparisonsStore;
+use crate::utils::share_links_store::ShareLinksStore;
 
 #[allow(dead_code)]
 pub struct AppState {
@@ -13,15 +21,32 @@ pub struct AppState {
     pub favorites_store: Arc<FavoritesStore>,
     pub carts_store: Arc<CartStore>,
     pub promocodes_store: Arc<PromoCodesStore>,
+    pub products_store: Arc<ProductStore>,
+    pub exchange_rates_store: Arc<ExchangeRatesStore>,
+    pub reviews_store: Arc<ReviewsStore>,
+    pub banned_words_store: Arc<BannedWordsStore>,
+    pub categories_store: Arc<CategoriesStore>,
+    pub recently_viewed_store: Arc<RecentlyViewedStore>,
+    pub comparisons_store: Arc<ComparisonsStore>,
+    pub share_links_store: Arc<ShareLinksStore>,
 }
 
 impl AppState {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         users_store: Arc<UserStore>,
         orders_store: Arc<OrdersStore>,
         favorites_store: Arc<FavoritesStore>,
         carts_store: Arc<CartStore>,
         promocodes_store: Arc<PromoCodesStore>,
+        products_store: Arc<ProductStore>,
+        exchange_rates_store: Arc<ExchangeRatesStore>,
+        reviews_store: Arc<ReviewsStore>,
+        banned_words_store: Arc<BannedWordsStore>,
+        categories_store: Arc<CategoriesStore>,
+        recently_viewed_store: Arc<RecentlyViewedStore>,
+        comparisons_store: Arc<ComparisonsStore>,
+        share_links_store: Arc<ShareLinksStore>,
     ) -> Self {
         AppState {
             users_store,
@@ -29,6 +54,14 @@ impl AppState {
             favorites_store,
             carts_store,
             promocodes_store,
+            products_store,
+            exchange_rates_store,
+            reviews_store,
+            banned_words_store,
+            categories_store,
+            recently_viewed_store,
+            comparisons_store,
+            share_links_store,
         }
     }
 }
\ No newline at end of file
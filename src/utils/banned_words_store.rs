@@ -0,0 +1,68 @@
+use log::info;
+use std::error::Error as StdError;
+use tokio::sync::Mutex;
+
+use crate::utils::file_watcher::ReloadDiff;
+use crate::utils::json_file::JsonFile;
+
+pub struct BannedWordsStore {
+    pub banned_words: Mutex<Vec<String>>,
+    pub file: JsonFile,
+}
+
+impl BannedWordsStore {
+    pub async fn new(banned_words_file_path: String) -> Result<Self, Box<dyn StdError>> {
+        let (file, banned_words) = JsonFile::open(banned_words_file_path, "[]").await?;
+        let banned_words = normalize_words(banned_words);
+        info!("Loaded {} banned words", banned_words.len());
+
+        Ok(BannedWordsStore {
+            banned_words: Mutex::new(banned_words),
+            file,
+        })
+    }
+
+    pub async fn reload(&self) -> Result<Option<ReloadDiff>, Box<dyn StdError>> {
+        let mut banned_words = self.banned_words.lock().await;
+
+        self.file.reload(|reloaded| {
+            let reloaded = normalize_words(reloaded);
+            let diff = ReloadDiff::between(&banned_words, &reloaded, |word| word.clone());
+
+            *banned_words = reloaded;
+            Ok(diff)
+        }).await
+    }
+
+    pub async fn find_matches(&self, text: &str) -> Vec<String> {
+        let banned_words = self.banned_words.lock().await;
+        let text = format!(" {} ", normalize_text(text));
+
+        banned_words
+            .iter()
+            .filter(|word| text.contains(&format!(" {} ", word)))
+            .cloned()
+            .collect()
+    }
+}
+
+fn normalize_text(text: &str) -> String {
+    text.to_lowercase()
+        .replace('ё', "е")
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn normalize_words(words: Vec<String>) -> Vec<String> {
+    let mut words: Vec<String> = words
+        .iter()
+        .map(|word| normalize_text(word))
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    words.sort();
+    words.dedup();
+    words
+}
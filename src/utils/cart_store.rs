@@ -1,14 +1,19 @@
-use tokio::fs::{File, OpenOptions, create_dir_all};
-use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
 use tokio::sync::Mutex;
-use std::path::Path;
+use std::collections::HashMap;
+use std::sync::Arc;
 use std::error::Error as StdError;
-use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use serde::{Deserialize, Deserializer, Serialize};
 use uuid::Uuid;
-use log::{info, error};
+use log::{error, info};
 
+use crate::models::money::Money;
 use crate::models::product::Product;
 use crate::utils::error::CustomError;
+use crate::utils::file_watcher::ReloadDiff;
+use crate::utils::json_file::JsonFile;
+use crate::utils::product_store::ProductStore;
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ProductWithCount {
@@ -16,141 +21,231 @@ pub struct ProductWithCount {
     pub(crate) count: u32,
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CartItem {
+    pub product_id: Uuid,
+    pub count: u32,
+    pub seen_price: Money,
+    #[serde(default)]
+    pub seen_discount: Option<f64>,
+}
+
+impl CartItem {
+    fn new(product: &Product, count: u32) -> Self {
+        CartItem {
+            product_id: product.uuid,
+            count,
+            seen_price: product.price.clone(),
+            seen_discount: product.discount,
+        }
+    }
+
+    fn seen_final_price(&self) -> Money {
+        match self.seen_discount {
+            Some(discount) if discount > 0.0 => Money::new(
+                self.seen_price.amount - self.seen_price.percentage(discount).amount,
+                &self.seen_price.currency,
+            ),
+            _ => self.seen_price.clone(),
+        }
+    }
+
+    fn change(&self, product: Option<&Product>) -> Option<CartChange> {
+        let previous_price = self.seen_final_price();
+
+        let Some(product) = product else {
+            return Some(CartChange {
+                product_id: self.product_id,
+                kind: CartChangeKind::ProductRemoved,
+                previous_price,
+                current_price: None,
+            });
+        };
+
+        let current_price = product.final_price();
+        let discount_ended = self.seen_discount.is_some_and(|d| d > 0.0) && !product.discount.is_some_and(|d| d > 0.0);
+
+        let kind = if discount_ended {
+            CartChangeKind::DiscountEnded
+        } else if current_price == previous_price {
+            return None;
+        } else if current_price.amount > previous_price.amount {
+            CartChangeKind::PriceIncreased
+        } else {
+            CartChangeKind::PriceDecreased
+        };
+
+        Some(CartChange {
+            product_id: self.product_id,
+            kind,
+            previous_price,
+            current_price: Some(current_price),
+        })
+    }
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CartChangeKind {
+    PriceIncreased,
+    PriceDecreased,
+    DiscountEnded,
+    ProductRemoved,
+}
+
+#[derive(Serialize)]
+pub struct CartChange {
+    pub product_id: Uuid,
+    pub kind: CartChangeKind,
+    pub previous_price: Money,
+    pub current_price: Option<Money>,
+}
+
+impl CartChange {
+    pub fn amounts_mut(&mut self) -> impl Iterator<Item = &mut Money> {
+        std::iter::once(&mut self.previous_price).chain(self.current_price.as_mut())
+    }
+}
+
+#[derive(Serialize)]
+pub struct ValidatedCart {
+    pub items: Vec<ProductWithCount>,
+    pub changes: Vec<CartChange>,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Cart {
     pub user_id: Uuid,
-    pub items: Vec<ProductWithCount>,
+    #[serde(default)]
+    pub guest: bool,
+    #[serde(deserialize_with = "deserialize_cart_items")]
+    pub items: Vec<CartItem>,
+    #[serde(default)]
+    pub saved_for_later: Vec<CartItem>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
 }
 
-pub struct CartStore {
-    pub carts: Mutex<Vec<Cart>>,
-    pub carts_file_path: String,
-    pub products_file_path: String,
+impl Cart {
+    fn new(user_id: Uuid, guest: bool) -> Self {
+        Cart {
+            user_id,
+            guest,
+            items: Vec::new(),
+            saved_for_later: Vec::new(),
+            updated_at: Some(Utc::now().to_rfc3339()),
+        }
+    }
+
+    fn touch(&mut self) {
+        self.updated_at = Some(Utc::now().to_rfc3339());
+    }
 }
 
-impl CartStore {
-    pub async fn new(carts_file_path: String, products_file_path: String) -> Result<Self, Box<dyn StdError>> {
-        let path = Path::new(&carts_file_path);
+#[derive(Clone, Copy)]
+pub struct CartExpiry {
+    pub user_days: u32,
+    pub guest_days: u32,
+}
 
-        if let Some(parent) = path.parent() {
-            create_dir_all(parent).await.expect("Failed to create directories for carts.json file");
+fn cart_mut(carts: &mut Vec<Cart>, user_id: Uuid, guest: bool) -> &mut Cart {
+    let cart = match carts.iter().position(|c| c.user_id == user_id) {
+        Some(pos) => &mut carts[pos],
+        None => {
+            carts.push(Cart::new(user_id, guest));
+            carts.last_mut().unwrap()
         }
+    };
 
-        if !path.exists() {
-            let mut file = File::create(path).await.expect("Failed to create carts.json file");
-            file.write_all(b"[]").await.expect("Failed to write empty array to file");
-        }
+    cart.touch();
+    cart
+}
 
-        let file = File::open(path).await.expect("Failed to open carts.json file");
-        let mut reader = BufReader::new(file);
-        let mut data = String::new();
-        reader.read_to_string(&mut data).await.expect("Failed to read file");
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CartItemRecord {
+    Reference(CartItem),
+    Legacy(Box<ProductWithCount>),
+}
 
-        let carts: Vec<Cart> = serde_json::from_str(&data)?;
+fn deserialize_cart_items<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<CartItem>, D::Error> {
+    let records = Vec::<CartItemRecord>::deserialize(deserializer)?;
+
+    Ok(records
+        .into_iter()
+        .map(|record| match record {
+            CartItemRecord::Reference(item) => item,
+            CartItemRecord::Legacy(item) => CartItem::new(&item.product, item.count),
+        })
+        .collect())
+}
+
+#[derive(Deserialize)]
+pub struct CartQuantityRequest {
+    pub count: u32,
+}
+
+#[derive(Deserialize)]
+pub struct CartItemUpdate {
+    pub product_id: Uuid,
+    pub count: u32,
+}
+
+#[derive(Deserialize)]
+pub struct CartUpdateRequest {
+    pub items: Vec<CartItemUpdate>,
+}
+
+pub struct CartStore {
+    pub carts: Mutex<Vec<Cart>>,
+    pub file: JsonFile,
+    pub products_store: Arc<ProductStore>,
+}
+
+impl CartStore {
+    pub async fn new(carts_file_path: String, products_store: Arc<ProductStore>) -> Result<Self, Box<dyn StdError>> {
+        let (file, carts): (JsonFile, Vec<Cart>) = JsonFile::open(carts_file_path, "[]").await?;
 
         Ok(CartStore {
             carts: Mutex::new(carts),
-            carts_file_path,
-            products_file_path,
+            file,
+            products_store,
         })
     }
 
     pub async fn save(&self) -> Result<(), Box<dyn StdError>> {
         let carts = self.carts.lock().await;
-        let data = serde_json::to_string_pretty(&*carts)?;
-
-        let mut file = OpenOptions::new()
-            .write(true)
-            .truncate(true)
-            .create(true)
-            .open(&self.carts_file_path)
-            .await
-            .expect("Failed to open carts.json file for writing");
-
-        file.write_all(data.as_bytes()).await?;
+        self.file.write(&*carts).await?;
         info!("Carts successfully saved.");
         Ok(())
     }
 
-    async fn load_product_by_id(&self, product_id: Uuid) -> Result<Product, Box<CustomError>> {
-        let mut file = match File::open(&self.products_file_path).await {
-            Ok(f) => {
-                info!("Product file successfully opened");
-                f
-            },
-            Err(e) => {
-                error!("Failed to open product file: {}", e);
-                return Err(Box::new(CustomError {
-                    message: "Product file not found".to_string(),
-                    error_code: "PRODUCT_FILE_NOT_FOUND".to_string(),
-                }));
-            }
-        };
-
-        let mut data = String::new();
-        match file.read_to_string(&mut data).await {
-            Ok(_) => info!("Product data successfully read"),
-            Err(e) => {
-                error!("Error reading product file: {}", e);
-                return Err(Box::new(CustomError {
-                    message: "Error reading product file".to_string(),
-                    error_code: "READ_ERROR".to_string(),
-                }));
-            }
-        }
-
-        let products: Vec<Product> = match serde_json::from_str(&data) {
-            Ok(p) => {
-                info!("Product JSON successfully deserialized");
-                p
-            },
-            Err(e) => {
-                error!("Error deserializing product JSON: {}", e);
-                return Err(Box::new(CustomError {
-                    message: "Error deserializing product JSON".to_string(),
-                    error_code: "DESERIALIZATION_ERROR".to_string(),
-                }));
-            }
-        };
+    pub async fn reload(&self) -> Result<Option<ReloadDiff>, Box<dyn StdError>> {
+        let mut carts = self.carts.lock().await;
 
-        products.into_iter().find(|p| p.uuid == product_id)
-            .ok_or_else(|| {
-                error!("Product with ID {} not found", product_id);
-                Box::new(CustomError {
-                    message: "Product not found".to_string(),
-                    error_code: "PRODUCT_NOT_FOUND".to_string(),
-                })
-            })
+        self.file.reload(|reloaded: Vec<Cart>| {
+            let diff = ReloadDiff::between(&carts, &reloaded, |c| c.user_id);
+            *carts = reloaded;
+            Ok(diff)
+        }).await
     }
 
-    pub async fn add_product_to_cart(&self, user_id: Uuid, product_id: Uuid) -> Result<(), Box<dyn StdError>> {
-        let product = self.load_product_by_id(product_id).await?;
+    pub async fn add_product_to_cart(&self, user_id: Uuid, guest: bool, product_id: Uuid) -> Result<(), Box<dyn StdError>> {
+        let product = self.products_store.get_sellable_product(product_id).await?;
 
         let mut carts = self.carts.lock().await;
 
-        let cart = carts.iter_mut().find(|c| c.user_id == user_id);
-        match cart {
-            Some(c) => {
-                if let Some(item) = c.items.iter_mut().find(|p| p.product.uuid == product.uuid) {
-                    item.count += 1;
-                } else {
-                    c.items.push(ProductWithCount {
-                        product,
-                        count: 1,
-                    });
-                }
-            }
-            None => {
-                let new_cart = Cart {
-                    user_id,
-                    items: vec![ProductWithCount {
-                        product,
-                        count: 1,
-                    }],
-                };
+        let in_cart = carts
+            .iter()
+            .find(|c| c.user_id == user_id)
+            .and_then(|c| c.items.iter().find(|p| p.product_id == product.uuid))
+            .map_or(0, |item| item.count);
+        product.ensure_available(in_cart.saturating_add(1))?;
 
-                carts.push(new_cart);
-            }
+        let cart = cart_mut(&mut carts, user_id, guest);
+        match cart.items.iter_mut().find(|p| p.product_id == product.uuid) {
+            Some(item) => item.count = item.count.saturating_add(1),
+            None => cart.items.push(CartItem::new(&product, 1)),
         }
 
         drop(carts);
@@ -158,44 +253,389 @@ impl CartStore {
         Ok(())
     }
 
-    pub async fn get_cart(&self, user_id: Uuid) -> Vec<ProductWithCount> {
+    pub async fn get_cart(&self, user_id: Uuid) -> Vec<CartItem> {
         let carts = self.carts.lock().await;
         carts.iter().find(|c| c.user_id == user_id).map(|c| c.items.clone()).unwrap_or_default()
     }
 
+    pub async fn get_saved_for_later(&self, user_id: Uuid) -> Vec<CartItem> {
+        let carts = self.carts.lock().await;
+        carts.iter().find(|c| c.user_id == user_id).map(|c| c.saved_for_later.clone()).unwrap_or_default()
+    }
+
+    pub async fn validate_cart(&self, user_id: Uuid, product_ids: Option<&[Uuid]>) -> ValidatedCart {
+        self.validate_items(self.get_cart(user_id).await, product_ids).await
+    }
+
+    pub async fn validate_saved_for_later(&self, user_id: Uuid) -> ValidatedCart {
+        self.validate_items(self.get_saved_for_later(user_id).await, None).await
+    }
+
+    async fn validate_items(&self, items: Vec<CartItem>, product_ids: Option<&[Uuid]>) -> ValidatedCart {
+        let items: Vec<CartItem> = items
+            .into_iter()
+            .filter(|item| product_ids.is_none_or(|ids| ids.contains(&item.product_id)))
+            .collect();
+        let mut products = self.resolved_products(&items).await;
+        let mut validated = ValidatedCart { items: Vec::new(), changes: Vec::new() };
+
+        for item in items {
+            let product = products.remove(&item.product_id);
+
+            if let Some(change) = item.change(product.as_ref()) {
+                validated.changes.push(change);
+            }
+            if let Some(product) = product {
+                validated.items.push(ProductWithCount { product, count: item.count });
+            }
+        }
+
+        validated
+    }
+
+    async fn resolved_products(&self, items: &[CartItem]) -> HashMap<Uuid, Product> {
+        let ids: Vec<Uuid> = items.iter().map(|item| item.product_id).collect();
+
+        self.products_store
+            .resolved_products(&ids)
+            .await
+            .into_iter()
+            .map(|product| (product.uuid, product))
+            .collect()
+    }
+
+    pub async fn acknowledge_changes(&self, user_id: Uuid) -> Result<(), Box<dyn StdError>> {
+        let mut carts = self.carts.lock().await;
+
+        let Some(cart) = carts.iter_mut().find(|c| c.user_id == user_id) else {
+            return Ok(());
+        };
+
+        let products = self.resolved_products(&cart.items).await;
+        let mut changed = false;
+
+        cart.items.retain_mut(|item| {
+            let product = products.get(&item.product_id);
+
+            if item.change(product).is_some() {
+                changed = true;
+            }
+
+            match product {
+                Some(product) => {
+                    item.seen_price = product.price.clone();
+                    item.seen_discount = product.discount;
+                    true
+                }
+                None => false,
+            }
+        });
+
+        if !changed {
+            return Ok(());
+        }
+
+        cart.touch();
+        drop(carts);
+        self.save().await
+    }
+
     pub async fn remove_product_from_cart(&self, user_id: Uuid, product_id: Uuid) -> Result<(), Box<dyn StdError>> {
         let mut carts = self.carts.lock().await;
 
         if let Some(cart) = carts.iter_mut().find(|c| c.user_id == user_id) {
-            if let Some(item) = cart.items.iter_mut().find(|p| p.product.uuid == product_id) {
+            if let Some(item) = cart.items.iter_mut().find(|p| p.product_id == product_id) {
+                cart.updated_at = Some(Utc::now().to_rfc3339());
+
                 if item.count > 1 {
                     item.count -= 1;
                 } else {
-                    cart.items.retain(|p| p.product.uuid != product_id);
+                    cart.items.retain(|p| p.product_id != product_id);
                 }
 
                 drop(carts);
                 self.save().await?;
                 Ok(())
             } else {
-                return Err(Box::new(CustomError {
+                Err(Box::new(CustomError {
                     message: "Product not found in cart".to_string(),
                     error_code: "PRODUCT_NOT_IN_CART".to_string(),
-                }));
+                }))
             }
         } else {
-            return Err(Box::new(CustomError {
+            Err(Box::new(CustomError {
                 message: "User cart not found".to_string(),
                 error_code: "USER_CART_NOT_FOUND".to_string(),
-            }));
+            }))
         }
     }
 
+    pub async fn set_product_quantity(&self, user_id: Uuid, guest: bool, product_id: Uuid, count: u32) -> Result<(), Box<dyn StdError>> {
+        self.update_cart(user_id, guest, vec![CartItemUpdate { product_id, count }]).await
+    }
+
+    pub async fn update_cart(&self, user_id: Uuid, guest: bool, updates: Vec<CartItemUpdate>) -> Result<(), Box<dyn StdError>> {
+        if updates.is_empty() {
+            return Err(Box::new(CustomError::new("No cart items to update", "EMPTY_CART_UPDATE")));
+        }
+
+        let mut products = Vec::with_capacity(updates.len());
+
+        for (i, update) in updates.iter().enumerate() {
+            if updates[..i].iter().any(|u| u.product_id == update.product_id) {
+                return Err(Box::new(CustomError::new(
+                    &format!("Product {} is listed more than once", update.product_id),
+                    "DUPLICATE_CART_ITEM",
+                )));
+            }
+
+            if update.count == 0 {
+                products.push(None);
+                continue;
+            }
+
+            let product = self.products_store.get_sellable_product(update.product_id).await?;
+            product.ensure_available(update.count)?;
+            products.push(Some(product));
+        }
+
+        let mut carts = self.carts.lock().await;
+        let cart = cart_mut(&mut carts, user_id, guest);
+
+        for (update, product) in updates.into_iter().zip(products) {
+            match product {
+                None => cart.items.retain(|item| item.product_id != update.product_id),
+                Some(product) => match cart.items.iter_mut().find(|item| item.product_id == product.uuid) {
+                    Some(item) => item.count = update.count,
+                    None => cart.items.push(CartItem::new(&product, update.count)),
+                },
+            }
+        }
+
+        drop(carts);
+        self.save().await
+    }
+
+    pub async fn clear_cart(&self, user_id: Uuid) -> Result<(), Box<dyn StdError>> {
+        let mut carts = self.carts.lock().await;
+
+        match carts.iter_mut().find(|c| c.user_id == user_id) {
+            Some(cart) if !cart.items.is_empty() => {
+                cart.items.clear();
+                cart.touch();
+            }
+            _ => return Ok(()),
+        }
+
+        drop(carts);
+        self.save().await
+    }
+
+    pub async fn merge_guest_cart(&self, guest_id: Uuid, user_id: Uuid) -> Result<(), Box<dyn StdError>> {
+        let guest_items = self.get_cart(guest_id).await;
+        let guest_saved = self.get_saved_for_later(guest_id).await;
+
+        if guest_items.is_empty() && guest_saved.is_empty() {
+            return self.clear_guest_cart(guest_id).await;
+        }
+
+        let mut available = Vec::with_capacity(guest_items.len());
+        for item in &guest_items {
+            let stock = self.products_store.get_product(item.product_id).await.map(|p| p.stock);
+            available.push(stock);
+        }
+
+        let mut carts = self.carts.lock().await;
+        carts.retain(|c| c.user_id != guest_id);
+        let cart = cart_mut(&mut carts, user_id, false);
+
+        for (guest_item, stock) in guest_items.into_iter().zip(available) {
+            let Some(stock) = stock else {
+                continue;
+            };
+
+            match cart.items.iter_mut().find(|item| item.product_id == guest_item.product_id) {
+                Some(item) => item.count = cap_to_stock(item.count.saturating_add(guest_item.count), stock),
+                None => cart.items.push(CartItem {
+                    count: cap_to_stock(guest_item.count, stock),
+                    ..guest_item
+                }),
+            }
+        }
+
+        cart.items.retain(|item| item.count > 0);
+
+        for saved in guest_saved {
+            let known = cart.items.iter().chain(&cart.saved_for_later).any(|item| item.product_id == saved.product_id);
+            if !known {
+                cart.saved_for_later.push(saved);
+            }
+        }
+
+        drop(carts);
+        self.save().await
+    }
+
+    async fn clear_guest_cart(&self, guest_id: Uuid) -> Result<(), Box<dyn StdError>> {
+        let mut carts = self.carts.lock().await;
+        let initial_len = carts.len();
+        carts.retain(|c| c.user_id != guest_id);
+
+        if carts.len() == initial_len {
+            return Ok(());
+        }
+
+        drop(carts);
+        self.save().await
+    }
+
+    pub async fn copy_items_to_cart(&self, user_id: Uuid, guest: bool, items: Vec<ProductWithCount>) -> Result<Vec<Uuid>, Box<dyn StdError>> {
+        let mut sellable = Vec::with_capacity(items.len());
+        let mut skipped = Vec::new();
+
+        for item in items {
+            match self.products_store.get_sellable_product(item.product.uuid).await {
+                Ok(product) if product.stock != Some(0) => sellable.push((product, item.count)),
+                _ => skipped.push(item.product.uuid),
+            }
+        }
+
+        if sellable.is_empty() {
+            return Ok(skipped);
+        }
+
+        let mut carts = self.carts.lock().await;
+        let cart = cart_mut(&mut carts, user_id, guest);
+
+        for (product, count) in sellable {
+            match cart.items.iter_mut().find(|item| item.product_id == product.uuid) {
+                Some(item) => item.count = cap_to_stock(item.count.saturating_add(count), product.stock),
+                None => cart.items.push(CartItem::new(&product, cap_to_stock(count, product.stock))),
+            }
+        }
+
+        drop(carts);
+        self.save().await?;
+        Ok(skipped)
+    }
+
+    pub async fn save_for_later(&self, user_id: Uuid, product_id: Uuid) -> Result<(), Box<dyn StdError>> {
+        let mut carts = self.carts.lock().await;
+
+        let Some(cart) = carts.iter_mut().find(|c| c.user_id == user_id) else {
+            return Err(Box::new(CustomError::new("User cart not found", "USER_CART_NOT_FOUND")));
+        };
+        let Some(pos) = cart.items.iter().position(|item| item.product_id == product_id) else {
+            return Err(Box::new(CustomError::new("Product not found in cart", "PRODUCT_NOT_IN_CART")));
+        };
+
+        let item = cart.items.remove(pos);
+        cart.saved_for_later.retain(|saved| saved.product_id != product_id);
+        cart.saved_for_later.push(item);
+        cart.touch();
+
+        drop(carts);
+        self.save().await
+    }
+
+    pub async fn move_to_cart(&self, user_id: Uuid, product_id: Uuid) -> Result<(), Box<dyn StdError>> {
+        let Some(saved) = self.get_saved_for_later(user_id).await.into_iter().find(|item| item.product_id == product_id) else {
+            return Err(Box::new(CustomError::new("Product not found in saved for later", "PRODUCT_NOT_SAVED")));
+        };
+
+        let product = self.products_store.get_sellable_product(product_id).await?;
+        let in_cart = self.get_cart(user_id).await.iter().find(|item| item.product_id == product_id).map_or(0, |item| item.count);
+        product.ensure_available(in_cart.saturating_add(saved.count))?;
+
+        let mut carts = self.carts.lock().await;
+
+        if let Some(cart) = carts.iter_mut().find(|c| c.user_id == user_id) {
+            cart.saved_for_later.retain(|item| item.product_id != product_id);
+
+            match cart.items.iter_mut().find(|item| item.product_id == product_id) {
+                Some(item) => item.count = item.count.saturating_add(saved.count),
+                None => cart.items.push(saved),
+            }
+            cart.touch();
+        }
+
+        drop(carts);
+        self.save().await
+    }
+
+    pub async fn remove_saved_item(&self, user_id: Uuid, product_id: Uuid) -> Result<(), Box<dyn StdError>> {
+        let mut carts = self.carts.lock().await;
+
+        let removed = carts
+            .iter_mut()
+            .find(|c| c.user_id == user_id)
+            .is_some_and(|cart| {
+                let initial_len = cart.saved_for_later.len();
+                cart.saved_for_later.retain(|item| item.product_id != product_id);
+                cart.touch();
+                cart.saved_for_later.len() < initial_len
+            });
+
+        if !removed {
+            return Err(Box::new(CustomError::new("Product not found in saved for later", "PRODUCT_NOT_SAVED")));
+        }
+
+        drop(carts);
+        self.save().await
+    }
+
+    pub async fn prune_expired(&self, expiry: CartExpiry) -> Result<usize, Box<dyn StdError>> {
+        let mut carts = self.carts.lock().await;
+        let now = Utc::now();
+        let mut changed = false;
+        let mut pruned = 0;
+
+        carts.retain_mut(|cart| {
+            let Some(updated_at) = cart.updated_at.as_deref().and_then(|t| t.parse::<DateTime<Utc>>().ok()) else {
+                cart.updated_at = Some(now.to_rfc3339());
+                changed = true;
+                return true;
+            };
+
+            let days = if cart.guest { expiry.guest_days } else { expiry.user_days };
+            if days == 0 || now - updated_at <= ChronoDuration::days(days as i64) {
+                return true;
+            }
+
+            if !cart.guest && cart.items.is_empty() && !cart.saved_for_later.is_empty() {
+                return true;
+            }
+
+            let kind = if cart.guest { "guest" } else { "user" };
+            let count: u32 = cart.items.iter().map(|item| item.count).sum();
+            info!("Pruned expired {} cart {} with {} items (last updated {})", kind, cart.user_id, count, updated_at.to_rfc3339());
+
+            pruned += 1;
+            changed = true;
+
+            if cart.guest || cart.saved_for_later.is_empty() {
+                return false;
+            }
+
+            cart.items.clear();
+            true
+        });
+
+        if !changed {
+            return Ok(0);
+        }
+
+        drop(carts);
+        self.save().await?;
+        Ok(pruned)
+    }
+
     pub async fn remove_products_from_cart(&self, user_id: Uuid, product_ids: Vec<Uuid>) -> Result<(), Box<dyn StdError>> {
         let mut carts = self.carts.lock().await;
 
         if let Some(cart) = carts.iter_mut().find(|c| c.user_id == user_id) {
-            cart.items.retain(|item| !product_ids.contains(&item.product.uuid));
+            cart.items.retain(|item| !product_ids.contains(&item.product_id));
+            cart.touch();
             drop(carts);
             self.save().await?;
             Ok(())
@@ -207,3 +647,23 @@ impl CartStore {
         }
     }
 }
+
+fn cap_to_stock(count: u32, stock: Option<u32>) -> u32 {
+    stock.map_or(count, |stock| count.min(stock))
+}
+
+pub fn spawn_cart_pruner(carts_store: Arc<CartStore>, expiry: CartExpiry, interval: Duration) {
+    actix_web::rt::spawn(async move {
+        info!("Pruning expired carts every {:?}", interval);
+
+        loop {
+            match carts_store.prune_expired(expiry).await {
+                Ok(0) => {}
+                Ok(pruned) => info!("Pruned {} expired carts", pruned),
+                Err(e) => error!("Failed to prune expired carts: {}", e),
+            }
+
+            actix_web::rt::time::sleep(interval).await;
+        }
+    });
+}
@@ -0,0 +1,382 @@
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error as StdError;
+use uuid::Uuid;
+
+use crate::models::money::{LegacyAmount, MoneyValue};
+use crate::models::product::{Product, ProductDraft};
+use crate::utils::error::CustomError;
+
+const CSV_COLUMNS: [&str; 18] = [
+    "uuid", "article", "pathurl", "name", "brand", "price", "currency", "discount",
+    "stock", "is_new", "rating", "reviews", "tags", "image", "description",
+    "parent", "options", "variant_axes",
+];
+
+const LIST_SEPARATOR: char = ';';
+const OPTION_SEPARATOR: char = '=';
+const ESCAPE: char = '\\';
+
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CatalogFormat {
+    Csv,
+    Jsonl,
+}
+
+impl CatalogFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().trim_start_matches('.').to_lowercase().as_str() {
+            "csv" | "text/csv" => Some(CatalogFormat::Csv),
+            "jsonl" | "ndjson" | "application/jsonl" | "application/x-ndjson" => Some(CatalogFormat::Jsonl),
+            _ => None,
+        }
+    }
+
+    pub fn from_path(path: &str) -> Option<Self> {
+        path.rsplit_once('.').and_then(|(_, extension)| CatalogFormat::from_name(extension))
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            CatalogFormat::Csv => "text/csv; charset=utf-8",
+            CatalogFormat::Jsonl => "application/x-ndjson",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            CatalogFormat::Csv => "csv",
+            CatalogFormat::Jsonl => "jsonl",
+        }
+    }
+}
+
+#[derive(Serialize, Clone)]
+pub struct RowError {
+    pub row: usize,
+    pub message: String,
+}
+
+#[derive(Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub rows: usize,
+    pub created: usize,
+    pub updated: usize,
+    pub errors: Vec<RowError>,
+}
+
+pub struct ParsedCatalog {
+    pub rows: Vec<(usize, ProductDraft)>,
+    pub errors: Vec<RowError>,
+}
+
+pub fn parse_catalog(format: CatalogFormat, data: &str) -> Result<ParsedCatalog, CustomError> {
+    match format {
+        CatalogFormat::Csv => parse_csv(data),
+        CatalogFormat::Jsonl => Ok(parse_jsonl(data)),
+    }
+}
+
+fn parse_jsonl(data: &str) -> ParsedCatalog {
+    let mut parsed = ParsedCatalog { rows: Vec::new(), errors: Vec::new() };
+
+    for (index, line) in data.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        match serde_json::from_str::<ProductDraft>(line) {
+            Ok(draft) => parsed.rows.push((index + 1, draft)),
+            Err(e) => parsed.errors.push(RowError { row: index + 1, message: e.to_string() }),
+        }
+    }
+
+    parsed
+}
+
+fn parse_csv(data: &str) -> Result<ParsedCatalog, CustomError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(data.trim_start_matches('\u{feff}').as_bytes());
+
+    let headers = reader
+        .headers()
+        .map_err(|e| CustomError::new(&format!("Invalid CSV header: {}", e), "INVALID_CATALOG_FILE"))?;
+
+    let columns: HashMap<&'static str, usize> = headers
+        .iter()
+        .enumerate()
+        .filter_map(|(pos, header)| column_name(header).map(|name| (name, pos)))
+        .collect();
+
+    for required in ["article", "pathurl", "name", "price"] {
+        if !columns.contains_key(required) {
+            return Err(CustomError::new(
+                &format!("CSV is missing the required `{}` column", required),
+                "INVALID_CATALOG_FILE",
+            ));
+        }
+    }
+
+    let mut parsed = ParsedCatalog { rows: Vec::new(), errors: Vec::new() };
+
+    for (index, record) in reader.records().enumerate() {
+        let row = index + 2;
+
+        let result = record
+            .map_err(|e| e.to_string())
+            .and_then(|record| {
+                let field = |name: &str| {
+                    columns
+                        .get(name)
+                        .and_then(|&pos| record.get(pos))
+                        .map(str::trim)
+                        .filter(|value| !value.is_empty())
+                        .map(str::to_string)
+                };
+
+                draft_from_fields(&field)
+            });
+
+        match result {
+            Ok(draft) => parsed.rows.push((row, draft)),
+            Err(message) => parsed.errors.push(RowError { row, message }),
+        }
+    }
+
+    Ok(parsed)
+}
+
+fn column_name(header: &str) -> Option<&'static str> {
+    let header = header.trim().to_lowercase().replace([' ', '-'], "_");
+
+    let name = match header.as_str() {
+        "id" | "uuid" => "uuid",
+        "article" | "sku" => "article",
+        "pathurl" | "path" | "url" | "slug" => "pathurl",
+        "name" | "title" => "name",
+        "brand" | "manufacturer" => "brand",
+        "price" => "price",
+        "currency" => "currency",
+        "discount" => "discount",
+        "stock" | "quantity" | "qty" => "stock",
+        "is_new" | "new" => "is_new",
+        "rating" => "rating",
+        "reviews" => "reviews",
+        "tags" | "tag" => "tags",
+        "image" | "image_url" => "image",
+        "description" => "description",
+        "parent" | "parent_uuid" | "parent_id" => "parent",
+        "options" | "variant_options" => "options",
+        "variant_axes" | "axes" => "variant_axes",
+        _ => return None,
+    };
+
+    Some(name)
+}
+
+fn draft_from_fields(field: &dyn Fn(&str) -> Option<String>) -> Result<ProductDraft, String> {
+    let required = |name: &str| field(name).ok_or_else(|| format!("`{}` is required", name));
+
+    Ok(ProductDraft {
+        uuid: parse_field(field("uuid"), "uuid", |v| v.parse::<Uuid>().ok())?,
+        pathurl: required("pathurl")?,
+        article: required("article")?,
+        price: MoneyValue::Legacy(LegacyAmount::Text(required("price")?)),
+        currency: field("currency"),
+        rating: parse_field(field("rating"), "rating", parse_number)?,
+        reviews: parse_field(field("reviews"), "reviews", parse_number)?,
+        discount: parse_field(field("discount"), "discount", parse_number)?,
+        is_new: parse_field(field("is_new"), "is_new", parse_bool)?,
+        stock: parse_field(field("stock"), "stock", |v| v.parse::<u32>().ok())?,
+        image: field("image"),
+        name: required("name")?,
+        brand: field("brand"),
+        tags: field("tags").map(|tags| split_list(&tags)),
+        description: field("description"),
+        parent: parse_field(field("parent"), "parent", |v| v.parse::<Uuid>().ok())?,
+        options: parse_field(field("options"), "options", parse_options)?,
+        variant_axes: field("variant_axes").map(|axes| split_list(&axes)),
+    })
+}
+
+/// Splits on unescaped `;` and unescapes each item; `\;`, `\=` and `\\` stand for the literal characters.
+fn split_list(value: &str) -> Vec<String> {
+    split_unescaped(value, LIST_SEPARATOR)
+        .iter()
+        .map(|item| unescape(item.trim()))
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+fn parse_options(value: &str) -> Option<BTreeMap<String, String>> {
+    split_unescaped(value, LIST_SEPARATOR)
+        .iter()
+        .filter(|pair| !pair.trim().is_empty())
+        .map(|pair| match split_unescaped(pair, OPTION_SEPARATOR).as_slice() {
+            [axis, option] => Some((unescape(axis.trim()), unescape(option.trim()))),
+            _ => None,
+        })
+        .collect()
+}
+
+fn split_unescaped(value: &str, separator: char) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        let current = parts.last_mut().expect("parts is never empty");
+
+        if c == ESCAPE {
+            current.push(c);
+            if let Some(escaped) = chars.next() {
+                current.push(escaped);
+            }
+        } else if c == separator {
+            parts.push(String::new());
+        } else {
+            current.push(c);
+        }
+    }
+
+    parts
+}
+
+fn unescape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c == ESCAPE {
+            result.extend(chars.next());
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+fn escape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        if c == ESCAPE || c == LIST_SEPARATOR || c == OPTION_SEPARATOR {
+            result.push(ESCAPE);
+        }
+        result.push(c);
+    }
+
+    result
+}
+
+fn join_list<'a>(items: impl IntoIterator<Item = &'a String>) -> String {
+    items.into_iter().map(|item| escape(item)).collect::<Vec<_>>().join(&LIST_SEPARATOR.to_string())
+}
+
+fn parse_field<T>(value: Option<String>, name: &str, parse: impl Fn(&str) -> Option<T>) -> Result<Option<T>, String> {
+    match value {
+        Some(value) => parse(&value)
+            .map(Some)
+            .ok_or_else(|| format!("Invalid `{}` value `{}`", name, value)),
+        None => Ok(None),
+    }
+}
+
+fn parse_number(value: &str) -> Option<f64> {
+    value.trim_end_matches('%').replace(',', ".").parse().ok()
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "true" | "yes" | "y" | "1" | "да" => Some(true),
+        "false" | "no" | "n" | "0" | "нет" => Some(false),
+        _ => None,
+    }
+}
+
+pub fn export_catalog(format: CatalogFormat, products: &[Product]) -> Result<String, Box<dyn StdError>> {
+    match format {
+        CatalogFormat::Csv => export_csv(products),
+        CatalogFormat::Jsonl => {
+            let mut data = String::new();
+
+            for product in products {
+                data.push_str(&serde_json::to_string(product)?);
+                data.push('\n');
+            }
+
+            Ok(data)
+        }
+    }
+}
+
+fn export_csv(products: &[Product]) -> Result<String, Box<dyn StdError>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(CSV_COLUMNS)?;
+
+    for product in products {
+        writer.write_record([
+            product.uuid.to_string(),
+            product.article.clone(),
+            product.pathurl.clone(),
+            product.name.clone(),
+            product.brand.clone(),
+            product.price.amount_string(),
+            product.price.currency.clone(),
+            product.discount.map(|d| d.to_string()).unwrap_or_default(),
+            product.stock.map(|s| s.to_string()).unwrap_or_default(),
+            product.is_new.to_string(),
+            product.rating.to_string(),
+            product.reviews.to_string(),
+            join_list(&product.tags),
+            product.image.clone(),
+            product.description.clone().unwrap_or_default(),
+            product.parent.map(|p| p.to_string()).unwrap_or_default(),
+            product
+                .options
+                .iter()
+                .map(|(axis, option)| format!("{}{}{}", escape(axis), OPTION_SEPARATOR, escape(option)))
+                .collect::<Vec<_>>()
+                .join(&LIST_SEPARATOR.to_string()),
+            join_list(&product.variant_axes),
+        ])?;
+    }
+
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_export_round_trips_list_values_containing_delimiters() {
+        let mut product = Product::test_fixture("Product", "100");
+        product.tags = ["a;b", "c,d", "e|f", "g=h", "back\\slash"].map(String::from).to_vec();
+        product.parent = Some(Uuid::new_v4());
+        product.options = BTreeMap::from([
+            ("size=fit".to_string(), "S;M".to_string()),
+            ("color".to_string(), "black, white".to_string()),
+        ]);
+
+        let data = export_catalog(CatalogFormat::Csv, std::slice::from_ref(&product)).unwrap();
+        let parsed = parse_catalog(CatalogFormat::Csv, &data).unwrap();
+
+        assert!(parsed.errors.is_empty());
+        let draft = &parsed.rows[0].1;
+        assert_eq!(draft.tags.as_ref(), Some(&product.tags));
+        assert_eq!(draft.options.as_ref(), Some(&product.options));
+    }
+
+    #[test]
+    fn list_values_split_only_on_unescaped_semicolons() {
+        assert_eq!(split_list("a; b,c |d;;"), vec!["a", "b,c |d"]);
+        assert_eq!(split_list("a\\;b;c"), vec!["a;b", "c"]);
+        assert_eq!(parse_options("color=black;size"), None);
+    }
+}
@@ -0,0 +1,211 @@
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::error::Error as StdError;
+use tokio::sync::Mutex;
+
+use crate::utils::error::CustomError;
+use crate::utils::file_watcher::ReloadDiff;
+use crate::utils::json_file::JsonFile;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CategoryNode {
+    pub slug: String,
+    pub name: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub children: Vec<CategoryNode>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct Breadcrumb {
+    pub slug: String,
+    pub name: String,
+}
+
+#[derive(Serialize)]
+pub struct CategorySummary {
+    pub slug: String,
+    pub name: String,
+    pub product_count: usize,
+    pub children: Vec<CategorySummary>,
+}
+
+#[derive(Serialize)]
+pub struct CategoryDetails {
+    #[serde(flatten)]
+    pub category: CategorySummary,
+    pub breadcrumbs: Vec<Breadcrumb>,
+}
+
+#[derive(Default)]
+struct CategoryTree {
+    roots: Vec<CategoryNode>,
+    paths: HashMap<String, Vec<Breadcrumb>>,
+    by_tag: HashMap<String, String>,
+}
+
+impl CategoryTree {
+    fn new(mut roots: Vec<CategoryNode>) -> Result<Self, CustomError> {
+        let mut tree = CategoryTree::default();
+
+        for node in roots.iter_mut() {
+            tree.index(node, &[])?;
+        }
+
+        tree.roots = roots;
+        Ok(tree)
+    }
+
+    fn index(&mut self, node: &mut CategoryNode, parents: &[Breadcrumb]) -> Result<(), CustomError> {
+        node.slug = node.slug.trim().to_lowercase();
+        node.name = node.name.trim().to_string();
+        node.tags = node.tags.iter().map(|tag| tag.trim().to_lowercase()).filter(|tag| !tag.is_empty()).collect();
+
+        if node.slug.is_empty() || node.name.is_empty() {
+            return Err(CustomError::new("Category slug and name cannot be empty", "INVALID_CATEGORY"));
+        }
+
+        if self.paths.contains_key(&node.slug) {
+            return Err(CustomError::new(
+                &format!("Category `{}` is defined more than once", node.slug),
+                "DUPLICATE_CATEGORY",
+            ));
+        }
+
+        for tag in &node.tags {
+            if let Some(other) = self.by_tag.insert(tag.clone(), node.slug.clone()) {
+                return Err(CustomError::new(
+                    &format!("Tag `{}` is mapped to both `{}` and `{}`", tag, other, node.slug),
+                    "DUPLICATE_CATEGORY_TAG",
+                ));
+            }
+        }
+
+        let mut path = parents.to_vec();
+        path.push(Breadcrumb {
+            slug: node.slug.clone(),
+            name: node.name.clone(),
+        });
+
+        for child in node.children.iter_mut() {
+            self.index(child, &path)?;
+        }
+
+        self.paths.insert(node.slug.clone(), path);
+        Ok(())
+    }
+
+    fn find(&self, slug: &str) -> Option<&CategoryNode> {
+        let slug = slug.trim().to_lowercase();
+        let path = self.paths.get(&slug)?;
+
+        let mut nodes = &self.roots;
+        let mut found = None;
+
+        for crumb in path {
+            let node = nodes.iter().find(|n| n.slug == crumb.slug)?;
+            nodes = &node.children;
+            found = Some(node);
+        }
+
+        found
+    }
+
+    fn breadcrumbs(&self, tags: &[String]) -> Vec<Breadcrumb> {
+        tags.iter()
+            .filter_map(|tag| self.by_tag.get(&tag.to_lowercase()))
+            .filter_map(|slug| self.paths.get(slug))
+            .max_by_key(|path| path.len())
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+fn subtree_tags(node: &CategoryNode) -> BTreeSet<String> {
+    let mut tags: BTreeSet<String> = node.tags.iter().cloned().collect();
+
+    for child in &node.children {
+        tags.extend(subtree_tags(child));
+    }
+
+    tags
+}
+
+fn summarize(node: &CategoryNode, products: &[BTreeSet<String>]) -> CategorySummary {
+    let tags = subtree_tags(node);
+
+    CategorySummary {
+        slug: node.slug.clone(),
+        name: node.name.clone(),
+        product_count: products.iter().filter(|product| !product.is_disjoint(&tags)).count(),
+        children: node.children.iter().map(|child| summarize(child, products)).collect(),
+    }
+}
+
+pub struct CategoriesStore {
+    categories: Mutex<CategoryTree>,
+    pub file: JsonFile,
+}
+
+impl CategoriesStore {
+    pub async fn new(categories_file_path: String) -> Result<Self, Box<dyn StdError>> {
+        let (file, categories) = JsonFile::open(categories_file_path, "[]").await?;
+        let categories = CategoryTree::new(categories)?;
+        info!("Loaded {} categories", categories.paths.len());
+
+        Ok(CategoriesStore {
+            categories: Mutex::new(categories),
+            file,
+        })
+    }
+
+    pub async fn reload(&self) -> Result<Option<ReloadDiff>, Box<dyn StdError>> {
+        let mut categories = self.categories.lock().await;
+
+        self.file.reload(|reloaded| {
+            let reloaded = CategoryTree::new(reloaded)?;
+            let old_paths: Vec<(&String, Vec<&String>)> = categories.paths
+                .iter()
+                .map(|(slug, path)| (slug, path.iter().map(|crumb| &crumb.name).collect()))
+                .collect();
+            let new_paths: Vec<(&String, Vec<&String>)> = reloaded.paths
+                .iter()
+                .map(|(slug, path)| (slug, path.iter().map(|crumb| &crumb.name).collect()))
+                .collect();
+            let diff = ReloadDiff::between(&old_paths, &new_paths, |(slug, _)| slug.to_string());
+
+            *categories = reloaded;
+            Ok(diff)
+        }).await
+    }
+
+    pub async fn get_tree(&self, products: &[BTreeSet<String>]) -> Vec<CategorySummary> {
+        let categories = self.categories.lock().await;
+        categories.roots.iter().map(|node| summarize(node, products)).collect()
+    }
+
+    pub async fn get_category(&self, slug: &str, products: &[BTreeSet<String>]) -> Option<CategoryDetails> {
+        let categories = self.categories.lock().await;
+        let node = categories.find(slug)?;
+
+        Some(CategoryDetails {
+            category: summarize(node, products),
+            breadcrumbs: categories.paths[&node.slug].clone(),
+        })
+    }
+
+    pub async fn category_tags(&self, slug: &str) -> Result<Vec<String>, CustomError> {
+        let categories = self.categories.lock().await;
+
+        categories
+            .find(slug)
+            .map(|node| subtree_tags(node).into_iter().collect())
+            .ok_or_else(|| CustomError::new(&format!("Category `{}` not found", slug.trim()), "CATEGORY_NOT_FOUND"))
+    }
+
+    pub async fn breadcrumbs(&self, tags: &[String]) -> Vec<Breadcrumb> {
+        self.categories.lock().await.breadcrumbs(tags)
+    }
+}
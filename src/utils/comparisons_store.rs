@@ -0,0 +1,222 @@
+use log::info;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error as StdError;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::models::product::Product;
+use crate::utils::error::CustomError;
+use crate::utils::file_watcher::ReloadDiff;
+use crate::utils::json_file::JsonFile;
+use crate::utils::product_store::ProductStore;
+use crate::utils::specs::parse_specs;
+
+type AttributeValue = fn(&Product) -> Value;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Comparison {
+    pub user_id: Uuid,
+    pub items: Vec<Uuid>,
+}
+
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum AttributeGroup {
+    General,
+    Options,
+    Specs,
+}
+
+#[derive(Serialize)]
+pub struct ComparedProduct {
+    pub uuid: Uuid,
+    pub name: String,
+    pub pathurl: String,
+    pub image: String,
+}
+
+#[derive(Serialize)]
+pub struct ComparisonAttribute {
+    pub group: AttributeGroup,
+    pub key: String,
+    pub values: Vec<Value>,
+    pub differs: bool,
+}
+
+#[derive(Serialize)]
+pub struct ProductComparison {
+    pub products: Vec<ComparedProduct>,
+    pub attributes: Vec<ComparisonAttribute>,
+    pub limit: usize,
+}
+
+impl ProductComparison {
+    pub fn new(products: &[Product], limit: usize) -> Self {
+        let specs: Vec<BTreeMap<String, String>> = products
+            .iter()
+            .map(|p| p.description.as_deref().map(parse_specs).unwrap_or_default())
+            .collect();
+
+        let mut attributes = Vec::new();
+        let mut push = |group, key: &str, values: Vec<Value>| {
+            let differs = values.iter().any(|v| v != &values[0]);
+            attributes.push(ComparisonAttribute { group, key: key.to_string(), values, differs });
+        };
+
+        let general: [(&str, AttributeValue); 7] = [
+            ("price", |p| serde_json::json!(p.price)),
+            ("final_price", |p| serde_json::json!(p.final_price())),
+            ("discount", |p| serde_json::json!(p.discount)),
+            ("rating", |p| serde_json::json!(p.rating)),
+            ("reviews", |p| serde_json::json!(p.reviews)),
+            ("brand", |p| serde_json::json!(p.brand)),
+            ("tags", |p| serde_json::json!(p.tags)),
+        ];
+
+        if !products.is_empty() {
+            for (key, value) in general {
+                push(AttributeGroup::General, key, products.iter().map(value).collect());
+            }
+        }
+
+        let option_keys: BTreeSet<&String> = products.iter().flat_map(|p| p.options.keys()).collect();
+        for key in option_keys {
+            push(AttributeGroup::Options, key, products.iter().map(|p| serde_json::json!(p.options.get(key))).collect());
+        }
+
+        let spec_keys: BTreeSet<&String> = specs.iter().flat_map(|s| s.keys()).collect();
+        for key in spec_keys {
+            push(AttributeGroup::Specs, key, specs.iter().map(|s| serde_json::json!(s.get(key))).collect());
+        }
+
+        ProductComparison {
+            products: products
+                .iter()
+                .map(|p| ComparedProduct {
+                    uuid: p.uuid,
+                    name: p.name.clone(),
+                    pathurl: p.pathurl.clone(),
+                    image: p.image.clone(),
+                })
+                .collect(),
+            attributes,
+            limit,
+        }
+    }
+}
+
+pub struct ComparisonsStore {
+    pub comparisons: Mutex<Vec<Comparison>>,
+    pub file: JsonFile,
+    pub products_store: Arc<ProductStore>,
+    pub limit: usize,
+}
+
+impl ComparisonsStore {
+    pub async fn new(comparisons_file_path: String, products_store: Arc<ProductStore>, limit: usize) -> Result<Self, Box<dyn StdError>> {
+        let (file, comparisons): (JsonFile, Vec<Comparison>) = JsonFile::open(comparisons_file_path, "[]").await?;
+
+        Ok(ComparisonsStore {
+            comparisons: Mutex::new(comparisons),
+            file,
+            products_store,
+            limit: limit.max(2),
+        })
+    }
+
+    pub async fn save(&self) -> Result<(), Box<dyn StdError>> {
+        let comparisons = self.comparisons.lock().await;
+        self.file.write(&*comparisons).await?;
+        info!("Comparisons successfully saved.");
+        Ok(())
+    }
+
+    pub async fn reload(&self) -> Result<Option<ReloadDiff>, Box<dyn StdError>> {
+        let mut comparisons = self.comparisons.lock().await;
+
+        self.file.reload(|reloaded: Vec<Comparison>| {
+            let diff = ReloadDiff::between(&comparisons, &reloaded, |c| c.user_id);
+            *comparisons = reloaded;
+            Ok(diff)
+        }).await
+    }
+
+    pub async fn add_product(&self, user_id: Uuid, product_id: Uuid) -> Result<(), Box<dyn StdError>> {
+        if self.products_store.get_product(product_id).await.is_none() {
+            return Err(Box::new(CustomError::new("Product not found", "PRODUCT_NOT_FOUND")));
+        }
+
+        let mut comparisons = self.comparisons.lock().await;
+
+        let comparison = match comparisons.iter().position(|c| c.user_id == user_id) {
+            Some(pos) => &mut comparisons[pos],
+            None => {
+                comparisons.push(Comparison { user_id, items: Vec::new() });
+                comparisons.last_mut().unwrap()
+            }
+        };
+
+        if comparison.items.contains(&product_id) {
+            return Err(Box::new(CustomError::new("Product already in comparison", "PRODUCT_ALREADY_IN_COMPARISON")));
+        }
+
+        if comparison.items.len() >= self.limit {
+            return Err(Box::new(CustomError::new(
+                &format!("Comparison cannot contain more than {} products", self.limit),
+                "COMPARISON_LIMIT_REACHED",
+            )));
+        }
+
+        comparison.items.push(product_id);
+
+        drop(comparisons);
+        self.save().await
+    }
+
+    pub async fn remove_product(&self, user_id: Uuid, product_id: Uuid) -> Result<(), Box<dyn StdError>> {
+        let mut comparisons = self.comparisons.lock().await;
+
+        let removed = comparisons
+            .iter_mut()
+            .find(|c| c.user_id == user_id)
+            .is_some_and(|c| {
+                let initial_len = c.items.len();
+                c.items.retain(|&uuid| uuid != product_id);
+                c.items.len() < initial_len
+            });
+
+        if !removed {
+            return Err(Box::new(CustomError::new("Product not found in comparison", "PRODUCT_NOT_FOUND_IN_COMPARISON")));
+        }
+
+        comparisons.retain(|c| !c.items.is_empty());
+
+        drop(comparisons);
+        self.save().await
+    }
+
+    pub async fn clear(&self, user_id: Uuid) -> Result<(), Box<dyn StdError>> {
+        let mut comparisons = self.comparisons.lock().await;
+        let initial_len = comparisons.len();
+        comparisons.retain(|c| c.user_id != user_id);
+
+        if comparisons.len() == initial_len {
+            return Ok(());
+        }
+
+        drop(comparisons);
+        self.save().await
+    }
+
+    pub async fn get_products(&self, user_id: Uuid) -> Vec<Product> {
+        let items = {
+            let comparisons = self.comparisons.lock().await;
+            comparisons.iter().find(|c| c.user_id == user_id).map(|c| c.items.clone()).unwrap_or_default()
+        };
+
+        self.products_store.resolved_products(&items).await
+    }
+}
@@ -0,0 +1,137 @@
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error as StdError;
+use tokio::sync::Mutex;
+
+use crate::models::money::{Money, DEFAULT_CURRENCY};
+use crate::models::product::Product;
+use crate::utils::error::CustomError;
+use crate::utils::file_watcher::ReloadDiff;
+use crate::utils::json_file::JsonFile;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExchangeRates {
+    pub base: String,
+    pub rates: BTreeMap<String, f64>,
+    pub updated_at: Option<String>,
+}
+
+impl ExchangeRates {
+    fn normalize(mut self) -> Result<Self, CustomError> {
+        self.base = self.base.trim().to_uppercase();
+        self.rates = self.rates
+            .into_iter()
+            .map(|(currency, rate)| (currency.trim().to_uppercase(), rate))
+            .collect();
+
+        if let Some((currency, _)) = self.rates.iter().find(|(_, rate)| !rate.is_finite() || **rate <= 0.0) {
+            return Err(CustomError::new(
+                &format!("Exchange rate for {} must be a positive number", currency),
+                "INVALID_EXCHANGE_RATE",
+            ));
+        }
+
+        self.rates.entry(self.base.clone()).or_insert(1.0);
+        Ok(self)
+    }
+
+    pub fn is_supported(&self, currency: &str) -> bool {
+        self.rates.contains_key(&currency.to_uppercase())
+    }
+
+    fn rate(&self, currency: &str) -> Result<f64, CustomError> {
+        self.rates.get(currency).copied().ok_or_else(|| {
+            CustomError::new(
+                &format!("No exchange rate configured for {}", currency),
+                "EXCHANGE_RATE_NOT_FOUND",
+            )
+        })
+    }
+
+    pub fn convert(&self, money: &Money, currency: &str) -> Result<Money, CustomError> {
+        let currency = currency.to_uppercase();
+
+        if money.currency == currency {
+            return Ok(money.clone());
+        }
+
+        let from = self.rate(&money.currency)?;
+        let to = self.rate(&currency)?;
+        let amount = (money.amount as f64 * to / from).round() as i64;
+
+        Ok(Money::new(amount, &currency))
+    }
+
+    pub fn convert_product(&self, product: &mut Product, currency: &str) -> Result<(), CustomError> {
+        product.price = self.convert(&product.price, currency)?;
+        Ok(())
+    }
+}
+
+impl Default for ExchangeRates {
+    fn default() -> Self {
+        ExchangeRates {
+            base: DEFAULT_CURRENCY.to_string(),
+            rates: BTreeMap::from([(DEFAULT_CURRENCY.to_string(), 1.0)]),
+            updated_at: None,
+        }
+    }
+}
+
+pub struct ExchangeRatesStore {
+    pub exchange_rates: Mutex<ExchangeRates>,
+    pub file: JsonFile,
+}
+
+impl ExchangeRatesStore {
+    pub async fn new(exchange_rates_file_path: String) -> Result<Self, Box<dyn StdError>> {
+        let default = serde_json::to_string_pretty(&ExchangeRates::default())?;
+        let (file, exchange_rates) = JsonFile::open(exchange_rates_file_path, &default).await?;
+        let exchange_rates = ExchangeRates::normalize(exchange_rates)?;
+        info!("Loaded {} exchange rates with base {}", exchange_rates.rates.len(), exchange_rates.base);
+
+        Ok(ExchangeRatesStore {
+            exchange_rates: Mutex::new(exchange_rates),
+            file,
+        })
+    }
+
+    pub async fn reload(&self) -> Result<Option<ReloadDiff>, Box<dyn StdError>> {
+        let mut exchange_rates = self.exchange_rates.lock().await;
+
+        self.file.reload(|reloaded| {
+            let reloaded = ExchangeRates::normalize(reloaded)?;
+            let old_rates: Vec<(&String, &f64)> = exchange_rates.rates.iter().collect();
+            let new_rates: Vec<(&String, &f64)> = reloaded.rates.iter().collect();
+            let diff = ReloadDiff::between(&old_rates, &new_rates, |(currency, _)| currency.to_string());
+
+            *exchange_rates = reloaded;
+            Ok(diff)
+        }).await
+    }
+
+    pub async fn get_rates(&self) -> ExchangeRates {
+        self.exchange_rates.lock().await.clone()
+    }
+
+    pub async fn resolve_currency(
+        &self,
+        requested: Option<&str>,
+        preferred: Option<&str>,
+    ) -> Result<Option<String>, CustomError> {
+        let currency = match requested.or(preferred).map(str::trim).filter(|c| !c.is_empty()) {
+            Some(currency) => currency.to_uppercase(),
+            None => return Ok(None),
+        };
+
+        if self.exchange_rates.lock().await.is_supported(&currency) {
+            Ok(Some(currency))
+        } else {
+            Err(CustomError::new(
+                &format!("Currency {} is not supported", currency),
+                "UNSUPPORTED_CURRENCY",
+            ))
+        }
+    }
+}
@@ -1,7 +1,5 @@
-use tokio::fs::{File, OpenOptions, create_dir_all};
-use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::sync::Mutex;
-use std::path::Path;
+use std::sync::Arc;
 use std::error::Error as StdError;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -9,6 +7,9 @@ use log::info;
 
 use crate::models::product::Product;
 use crate::utils::error::CustomError;
+use crate::utils::file_watcher::ReloadDiff;
+use crate::utils::json_file::JsonFile;
+use crate::utils::product_store::ProductStore;
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Favorites {
@@ -18,75 +19,40 @@ pub struct Favorites {
 
 pub struct FavoritesStore {
     pub favorites: Mutex<Vec<Favorites>>,
-    pub favorites_file_path: String,
-    pub products_file_path: String,
+    pub file: JsonFile,
+    pub products_store: Arc<ProductStore>,
 }
 
 impl FavoritesStore {
-    pub async fn new(favorites_file_path: String, products_file_path: String) -> Result<Self, Box<dyn StdError>> {
-        let path = Path::new(&favorites_file_path);
-
-        if let Some(parent) = path.parent() {
-            create_dir_all(parent).await.expect("Failed to create directories for favorites.json file");
-        }
-
-        if !path.exists() {
-            let mut file = File::create(path).await.expect("Failed to create favorites.json file");
-            file.write_all(b"[]").await.expect("Failed to write empty array to file");
-        }
-
-        let file = File::open(path).await.expect("Failed to open favorites.json file");
-        let mut reader = BufReader::new(file);
-        let mut data = String::new();
-        reader.read_to_string(&mut data).await.expect("Failed to read file");
-
-        let favorites: Vec<Favorites> = serde_json::from_str(&data)?;
+    pub async fn new(favorites_file_path: String, products_store: Arc<ProductStore>) -> Result<Self, Box<dyn StdError>> {
+        let (file, favorites): (JsonFile, Vec<Favorites>) = JsonFile::open(favorites_file_path, "[]").await?;
 
         Ok(FavoritesStore {
             favorites: Mutex::new(favorites),
-            favorites_file_path,
-            products_file_path,
+            file,
+            products_store,
         })
     }
 
     pub async fn save(&self) -> Result<(), Box<dyn StdError>> {
         let favorites = self.favorites.lock().await;
-        let data = serde_json::to_string_pretty(&*favorites)?;
-
-        let mut file = OpenOptions::new()
-            .write(true)
-            .truncate(true)
-            .create(true)
-            .open(&self.favorites_file_path)
-            .await
-            .expect("Failed to open favorites.json file for writing");
-
-        file.write_all(data.as_bytes()).await?;
+        self.file.write(&*favorites).await?;
         info!("Favorites successfully saved.");
         Ok(())
     }
 
-    async fn load_product_by_id(&self, product_id: Uuid) -> Result<Product, Box<dyn StdError>> {
-        let mut file = match File::open(&self.products_file_path).await {
-            Ok(f) => f,
-            Err(_) => {
-                return Err(Box::new(CustomError {
-                    message: "Product file not found".to_string(),
-                    error_code: "PRODUCT_FILE_NOT_FOUND".to_string(),
-                }));
-            }
-        };
-
-        let mut data = String::new();
-        file.read_to_string(&mut data).await?;
+    pub async fn reload(&self) -> Result<Option<ReloadDiff>, Box<dyn StdError>> {
+        let mut favorites = self.favorites.lock().await;
 
-        let products: Vec<Product> = serde_json::from_str(&data).unwrap_or_default();
-        products.into_iter().find(|p| p.uuid == product_id)
-            .ok_or_else(|| "Product not found".into())
+        self.file.reload(|reloaded: Vec<Favorites>| {
+            let diff = ReloadDiff::between(&favorites, &reloaded, |f| f.user_id);
+            *favorites = reloaded;
+            Ok(diff)
+        }).await
     }
 
     pub async fn add_product_to_favorites(&self, user_id: Uuid, product_id: Uuid) -> Result<(), Box<dyn StdError>> {
-        let product = self.load_product_by_id(product_id).await?;
+        let product = self.products_store.get_sellable_product(product_id).await?;
 
         let mut favorites = self.favorites.lock().await;
 
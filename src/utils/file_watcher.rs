@@ -0,0 +1,169 @@
+use actix_web::web;
+use log::{error, info};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::error::Error as StdError;
+use std::fmt;
+use std::hash::Hash;
+use std::time::{Duration, SystemTime};
+
+use crate::state::app_state::AppState;
+
+#[derive(Debug, Default)]
+pub struct ReloadDiff {
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+    pub total: usize,
+}
+
+impl ReloadDiff {
+    pub fn between<T, K, F>(old: &[T], new: &[T], key: F) -> Self
+    where
+        T: Serialize,
+        K: Eq + Hash,
+        F: Fn(&T) -> K,
+    {
+        let old_entries: HashMap<K, serde_json::Value> = old
+            .iter()
+            .map(|item| (key(item), serde_json::to_value(item).unwrap_or_default()))
+            .collect();
+
+        let mut diff = ReloadDiff {
+            total: new.len(),
+            ..Default::default()
+        };
+        let mut seen = 0;
+
+        for item in new {
+            match old_entries.get(&key(item)) {
+                Some(old_value) => {
+                    seen += 1;
+                    if *old_value != serde_json::to_value(item).unwrap_or_default() {
+                        diff.changed += 1;
+                    }
+                }
+                None => diff.added += 1,
+            }
+        }
+
+        diff.removed = old_entries.len().saturating_sub(seen);
+        diff
+    }
+}
+
+impl fmt::Display for ReloadDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "+{} added, -{} removed, ~{} changed ({} total)",
+            self.added, self.removed, self.changed, self.total
+        )
+    }
+}
+
+#[derive(Clone, Copy)]
+enum DataFile {
+    Users,
+    Carts,
+    Favorites,
+    Orders,
+    PromoCodes,
+    Products,
+    ExchangeRates,
+    Reviews,
+    BannedWords,
+    Categories,
+    RecentlyViewed,
+    Comparisons,
+    ShareLinks,
+}
+
+impl DataFile {
+    const ALL: [DataFile; 13] = [
+        DataFile::Users,
+        DataFile::Carts,
+        DataFile::Favorites,
+        DataFile::Orders,
+        DataFile::PromoCodes,
+        DataFile::Products,
+        DataFile::ExchangeRates,
+        DataFile::Reviews,
+        DataFile::BannedWords,
+        DataFile::Categories,
+        DataFile::RecentlyViewed,
+        DataFile::Comparisons,
+        DataFile::ShareLinks,
+    ];
+
+    fn path(self, app_state: &AppState) -> &str {
+        match self {
+            DataFile::Users => &app_state.users_store.file.path,
+            DataFile::Carts => &app_state.carts_store.file.path,
+            DataFile::Favorites => &app_state.favorites_store.file.path,
+            DataFile::Orders => &app_state.orders_store.file.path,
+            DataFile::PromoCodes => &app_state.promocodes_store.file.path,
+            DataFile::Products => &app_state.products_store.file.path,
+            DataFile::ExchangeRates => &app_state.exchange_rates_store.file.path,
+            DataFile::Reviews => &app_state.reviews_store.file.path,
+            DataFile::BannedWords => &app_state.banned_words_store.file.path,
+            DataFile::Categories => &app_state.categories_store.file.path,
+            DataFile::RecentlyViewed => &app_state.recently_viewed_store.file.path,
+            DataFile::Comparisons => &app_state.comparisons_store.file.path,
+            DataFile::ShareLinks => &app_state.share_links_store.file.path,
+        }
+    }
+
+    async fn reload(self, app_state: &AppState) -> Result<Option<ReloadDiff>, Box<dyn StdError>> {
+        match self {
+            DataFile::Users => app_state.users_store.reload().await,
+            DataFile::Carts => app_state.carts_store.reload().await,
+            DataFile::Favorites => app_state.favorites_store.reload().await,
+            DataFile::Orders => app_state.orders_store.reload().await,
+            DataFile::PromoCodes => app_state.promocodes_store.reload().await,
+            DataFile::Products => app_state.products_store.reload().await,
+            DataFile::ExchangeRates => app_state.exchange_rates_store.reload().await,
+            DataFile::Reviews => app_state.reviews_store.reload().await,
+            DataFile::BannedWords => app_state.banned_words_store.reload().await,
+            DataFile::Categories => app_state.categories_store.reload().await,
+            DataFile::RecentlyViewed => app_state.recently_viewed_store.reload().await,
+            DataFile::Comparisons => app_state.comparisons_store.reload().await,
+            DataFile::ShareLinks => app_state.share_links_store.reload().await,
+        }
+    }
+}
+
+async fn modified_at(path: &str) -> Option<SystemTime> {
+    tokio::fs::metadata(path).await.ok()?.modified().ok()
+}
+
+pub fn spawn_data_watcher(app_state: web::Data<AppState>, interval: Duration) {
+    actix_web::rt::spawn(async move {
+        let mut last_modified = Vec::with_capacity(DataFile::ALL.len());
+        for file in DataFile::ALL {
+            last_modified.push(modified_at(file.path(&app_state)).await);
+        }
+
+        info!("Watching data files for changes every {:?}", interval);
+
+        loop {
+            actix_web::rt::time::sleep(interval).await;
+
+            for (file, last) in DataFile::ALL.iter().zip(last_modified.iter_mut()) {
+                let path = file.path(&app_state);
+                let modified = modified_at(path).await;
+
+                if modified == *last {
+                    continue;
+                }
+                *last = modified;
+
+                match file.reload(&app_state).await {
+                    Ok(Some(diff)) => info!("Reloaded `{}` after external change: {}", path, diff),
+                    Ok(None) => {}
+                    Err(e) => error!("Failed to reload `{}`, keeping previous state: {}", path, e),
+                }
+            }
+        }
+    });
+}
@@ -1,3 +1,14 @@
+use actix_session::Session;
+use actix_web::{HttpRequest, HttpResponse};
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::models::money::Money;
+use crate::models::product::Product;
+use crate::state::app_state::AppState;
+use crate::utils::error::CustomError;
+
 pub fn mask_card_number(card_number: &str) -> String {
     let len = card_number.len();
     if len <= 4 {
@@ -6,3 +17,120 @@ pub fn mask_card_number(card_number: &str) -> String {
         "*".repeat(len - 4) + &card_number[len - 4..]
     }
 }
+
+pub struct Visitor {
+    pub id: Uuid,
+    pub guest: bool,
+}
+
+pub fn guest_id(session: &Session) -> Uuid {
+    if let Some(guest_id) = session.get::<Uuid>("guest_id").unwrap_or(None) {
+        return guest_id;
+    }
+
+    let guest_id = Uuid::new_v4();
+    if let Err(e) = session.insert("guest_id", guest_id) {
+        log::error!("Failed to store guest id in session: {}", e);
+    }
+    guest_id
+}
+
+pub fn current_visitor(session: &Session) -> Visitor {
+    match session.get::<Uuid>("user_id").unwrap_or(None) {
+        Some(user_id) => Visitor { id: user_id, guest: false },
+        None => Visitor { id: guest_id(session), guest: true },
+    }
+}
+
+const CRAWLER_MARKERS: [&str; 4] = ["bot", "crawler", "spider", "slurp"];
+
+/// Requests without a user agent or from a known crawler; these should not start guest sessions.
+pub fn is_crawler(req: &HttpRequest) -> bool {
+    match req.headers().get("User-Agent").and_then(|agent| agent.to_str().ok()) {
+        Some(agent) => {
+            let agent = agent.to_lowercase();
+            CRAWLER_MARKERS.iter().any(|marker| agent.contains(marker))
+        }
+        None => true,
+    }
+}
+
+/// Like `current_visitor`, but returns `None` instead of starting a guest session.
+pub fn existing_visitor(session: &Session) -> Option<Visitor> {
+    match session.get::<Uuid>("user_id").unwrap_or(None) {
+        Some(user_id) => Some(Visitor { id: user_id, guest: false }),
+        None => session
+            .get::<Uuid>("guest_id")
+            .unwrap_or(None)
+            .map(|guest_id| Visitor { id: guest_id, guest: true }),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct CurrencyQuery {
+    pub currency: Option<String>,
+}
+
+pub async fn display_currency(
+    session: &Session,
+    query: &CurrencyQuery,
+    app_state: &AppState,
+) -> Result<Option<String>, CustomError> {
+    let preferred = match session.get::<Uuid>("user_id").unwrap_or(None) {
+        Some(user_id) => app_state.users_store.find_user_by_id(user_id).await.and_then(|u| u.preferred_currency),
+        None => None,
+    };
+
+    app_state.exchange_rates_store.resolve_currency(query.currency.as_deref(), preferred.as_deref()).await
+}
+
+pub async fn localize_products<'a>(
+    products: impl IntoIterator<Item = &'a mut Product>,
+    session: &Session,
+    query: &CurrencyQuery,
+    app_state: &AppState,
+) -> Result<(), CustomError> {
+    if let Some(currency) = display_currency(session, query, app_state).await? {
+        let rates = app_state.exchange_rates_store.get_rates().await;
+
+        for product in products {
+            rates.convert_product(product, &currency)?;
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn localize_amounts<'a>(
+    amounts: impl IntoIterator<Item = &'a mut Money>,
+    session: &Session,
+    query: &CurrencyQuery,
+    app_state: &AppState,
+) -> Result<(), CustomError> {
+    if let Some(currency) = display_currency(session, query, app_state).await? {
+        let rates = app_state.exchange_rates_store.get_rates().await;
+
+        for amount in amounts {
+            *amount = rates.convert(amount, &currency)?;
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn require_admin(session: &Session, app_state: &AppState) -> Result<Uuid, HttpResponse> {
+    let user_id = session.get::<Uuid>("user_id").unwrap_or(None).ok_or_else(|| {
+        HttpResponse::Unauthorized().json(json!({
+            "message": "Unauthorized",
+            "errorCode": "UNAUTHORIZED_ACCESS"
+        }))
+    })?;
+
+    match app_state.users_store.find_user_by_id(user_id).await {
+        Some(user) if user.is_admin => Ok(user_id),
+        _ => Err(HttpResponse::Forbidden().json(json!({
+            "message": "Administrator access required",
+            "errorCode": "ADMIN_ACCESS_REQUIRED"
+        }))),
+    }
+}
@@ -0,0 +1,65 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::error::Error as StdError;
+use std::path::Path;
+use tokio::fs::{create_dir_all, read_to_string, rename, File};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+pub struct JsonFile {
+    pub path: String,
+    synced_data: Mutex<String>,
+}
+
+impl JsonFile {
+    /// Opens a data file, creating it with `default` contents if it is missing.
+    pub async fn open<T: DeserializeOwned>(path: String, default: &str) -> Result<(Self, T), Box<dyn StdError>> {
+        let file_path = Path::new(&path);
+
+        if let Some(parent) = file_path.parent() {
+            create_dir_all(parent).await.map_err(|e| format!("Failed to create directories for {}: {}", path, e))?;
+        }
+
+        if !file_path.exists() {
+            let mut file = File::create(file_path).await.map_err(|e| format!("Failed to create {}: {}", path, e))?;
+            file.write_all(default.as_bytes()).await?;
+        }
+
+        let data = read_to_string(file_path).await.map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        let value = serde_json::from_str(&data).map_err(|e| format!("Failed to parse {}: {}", path, e))?;
+
+        Ok((JsonFile { path, synced_data: Mutex::new(data) }, value))
+    }
+
+    pub async fn write<T: Serialize + ?Sized>(&self, value: &T) -> Result<(), Box<dyn StdError>> {
+        let data = serde_json::to_string_pretty(value)?;
+        let tmp_file_path = format!("{}.tmp", self.path);
+
+        let mut file = File::create(&tmp_file_path).await?;
+        file.write_all(data.as_bytes()).await?;
+        file.sync_all().await?;
+        rename(&tmp_file_path, &self.path).await?;
+
+        *self.synced_data.lock().await = data;
+        Ok(())
+    }
+
+    /// Re-reads the file and passes its contents to `apply` if they differ from what was last loaded or written.
+    /// Callers hold their data lock across this call so a concurrent save cannot interleave.
+    pub async fn reload<T, R, F>(&self, apply: F) -> Result<Option<R>, Box<dyn StdError>>
+    where
+        T: DeserializeOwned,
+        F: FnOnce(T) -> Result<R, Box<dyn StdError>>,
+    {
+        let data = read_to_string(&self.path).await?;
+        let mut synced_data = self.synced_data.lock().await;
+
+        if *synced_data == data {
+            return Ok(None);
+        }
+
+        let result = apply(serde_json::from_str(&data)?)?;
+        *synced_data = data;
+        Ok(Some(result))
+    }
+}
@@ -1,8 +1,23 @@
 pub mod logger;
 pub mod func;
 pub mod error;
+pub mod file_watcher;
+pub mod json_file;
 pub mod user_store;
 pub mod cart_store;
 pub mod orders_store;
 pub mod favorites_store;
-pub mod promo_codes_store;
\ No newline at end of file
+pub mod promo_codes_store;
+pub mod product_store;
+pub mod search_index;
+pub mod exchange_rates_store;
+pub mod catalog_io;
+pub mod reviews_store;
+pub mod banned_words_store;
+pub mod categories_store;
+pub mod recommender;
+pub mod recently_viewed_store;
+pub mod specs;
+pub mod comparisons_store;
+pub mod share_links_store;
+pub mod pricing;
\ No newline at end of file
@@ -1,19 +1,23 @@
 use chrono::Utc;
 use log::info;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::error::Error as StdError;
-use std::path::Path;
-use tokio::fs::{create_dir_all, File, OpenOptions};
-use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader};
+use std::sync::Arc;
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
+use crate::models::money::Money;
 use crate::models::product::Product;
 use crate::state::app_state::AppState;
 use crate::utils::cart_store::ProductWithCount;
 use crate::utils::error::CustomError;
+use crate::utils::file_watcher::ReloadDiff;
+use crate::utils::json_file::JsonFile;
+use crate::utils::pricing::{checkout_currency, price_items};
+use crate::utils::product_store::ProductStore;
+use crate::utils::recommender::Recommender;
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
 pub enum OrderStatus {
     Received,
     Canceled,
@@ -25,68 +29,105 @@ pub enum OrderStatus {
     PreparingForShipment,
 }
 
+impl OrderStatus {
+    pub fn is_cancelable(self) -> bool {
+        matches!(self, OrderStatus::Received | OrderStatus::PreparingForShipment)
+    }
+
+    pub fn is_returnable(self) -> bool {
+        matches!(
+            self,
+            OrderStatus::InTransit | OrderStatus::AtCustoms | OrderStatus::DisputeOpen | OrderStatus::DisputeClosed
+        )
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Order {
     pub order_id: Uuid,
     pub user_id: Uuid,
-    pub items: Vec<Product>,
-    pub total_price: f64,
+    #[serde(deserialize_with = "deserialize_order_items")]
+    pub items: Vec<ProductWithCount>,
+    pub total_price: Money,
     pub created_at: String,
-    pub discount: Option<f64>,
+    pub discount: Option<Money>,
     pub promo_code: Option<String>,
     pub delivery_address: String,
     pub payment_card_number: String,
     pub order_status: OrderStatus,
 }
 
-pub struct OrdersStore {
-    pub orders: Mutex<Vec<Order>>,
-    pub orders_file_path: String,
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OrderItemRecord {
+    WithCount(ProductWithCount),
+    Legacy(Product),
 }
 
-impl OrdersStore {
-    pub async fn new(orders_file_path: String) -> Result<Self, Box<dyn StdError>> {
-        let path = Path::new(&orders_file_path);
+fn deserialize_order_items<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<ProductWithCount>, D::Error> {
+    let records = Vec::<OrderItemRecord>::deserialize(deserializer)?;
 
-        if let Some(parent) = path.parent() {
-            create_dir_all(parent).await.expect("Failed to create directories for orders.json file");
-        }
+    Ok(records
+        .into_iter()
+        .map(|record| match record {
+            OrderItemRecord::WithCount(item) => item,
+            OrderItemRecord::Legacy(product) => ProductWithCount { product, count: 1 },
+        })
+        .collect())
+}
 
-        if !path.exists() {
-            let mut file = File::create(path).await.expect("Failed to create orders.json file");
-            file.write_all(b"[]").await.expect("Failed to write empty array to file");
-        }
+impl Order {
+    fn stock_items(&self) -> Vec<(Uuid, u32)> {
+        self.items.iter().map(|item| (item.product.uuid, item.count)).collect()
+    }
+}
 
-        let file = File::open(path).await.expect("Failed to open orders.json file");
-        let mut reader = BufReader::new(file);
-        let mut data = String::new();
-        reader.read_to_string(&mut data).await.expect("Failed to read file");
+pub struct OrdersStore {
+    pub orders: Mutex<Vec<Order>>,
+    pub file: JsonFile,
+    pub products_store: Arc<ProductStore>,
+    pub recommender: Arc<Recommender>,
+}
 
-        let orders: Vec<Order> = serde_json::from_str(&data)?;
+impl OrdersStore {
+    pub async fn new(
+        orders_file_path: String,
+        products_store: Arc<ProductStore>,
+        recommender: Arc<Recommender>,
+    ) -> Result<Self, Box<dyn StdError>> {
+        let (file, orders): (JsonFile, Vec<Order>) = JsonFile::open(orders_file_path, "[]").await?;
+        recommender.rebuild(&orders).await;
 
         Ok(OrdersStore {
             orders: Mutex::new(orders),
-            orders_file_path,
+            file,
+            products_store,
+            recommender,
         })
     }
 
     pub async fn save(&self) -> Result<(), Box<dyn StdError>> {
         let orders = self.orders.lock().await;
-        let data = serde_json::to_string_pretty(&*orders)?;
-
-        let mut file = OpenOptions::new()
-            .write(true)
-            .truncate(true)
-            .create(true)
-            .open(&self.orders_file_path)
-            .await
-            .expect("Failed to open orders.json file for writing");
-
-        file.write_all(data.as_bytes()).await?;
+        self.file.write(&*orders).await?;
         info!("Orders successfully saved.");
         Ok(())
     }
 
+    pub async fn reload(&self) -> Result<Option<ReloadDiff>, Box<dyn StdError>> {
+        let mut orders = self.orders.lock().await;
+
+        let diff = self.file.reload(|reloaded: Vec<Order>| {
+            let diff = ReloadDiff::between(&orders, &reloaded, |o| o.order_id);
+            *orders = reloaded;
+            Ok(diff)
+        }).await?;
+
+        if diff.is_some() {
+            self.recommender.rebuild(&orders).await;
+        }
+        Ok(diff)
+    }
+
     pub async fn add_order(
         &self,
         user_id: Uuid,
@@ -126,12 +167,16 @@ impl OrdersStore {
             user.username
         );
 
-        let cart_items = app_state.carts_store.get_cart(user_id).await;
+        let cart = app_state.carts_store.validate_cart(user_id, Some(&selected_product_ids)).await;
 
-        let selected_items: Vec<ProductWithCount> = cart_items
-            .into_iter()
-            .filter(|item| selected_product_ids.contains(&item.product.uuid))
-            .collect();
+        if !cart.changes.is_empty() {
+            return Err(Box::new(CustomError::new(
+                "Prices or availability of products in the cart have changed. Review and acknowledge the changes before placing the order.",
+                "CART_CHANGED",
+            )));
+        }
+
+        let selected_items = cart.items;
 
         if selected_items.is_empty() {
             return Err(Box::new(CustomError {
@@ -140,44 +185,34 @@ impl OrdersStore {
             }));
         }
 
-        let mut total_price: f64 = 0.0;
-        let mut total_discount: f64 = 0.0;
-
         for item in &selected_items {
-            let clean_price = item.product.price
-                .replace(",", "")
-                .replace(".", "")
-                .replace(" ", "")
-                .trim()
-                .to_string();
-
-            let mut price: f64 = clean_price.parse().map_err(|e| {
-                format!("Failed to parse price for product {}: {}", item.product.uuid, e)
-            })?;
+            let product = self.products_store.get_sellable_product(item.product.uuid).await?;
+            product.ensure_available(item.count)?;
+        }
 
-            if let Some(discount) = item.product.discount {
-                let discount_amount = price * (discount / 100.0);
-                price -= discount_amount;
-                total_discount += discount_amount * item.count as f64;
-            }
+        let stock_items: Vec<(Uuid, u32)> = selected_items
+            .iter()
+            .map(|item| (item.product.uuid, item.count))
+            .collect();
 
-            total_price += price * item.count as f64;
-        }
+        let currency = checkout_currency(user.preferred_currency.as_deref(), &selected_items);
+        let rates = app_state.exchange_rates_store.get_rates().await;
 
-        if let Some(code) = promo_code.clone() {
-            let (new_total_price, promo_discount) = app_state.promocodes_store.apply_promo_code(&code, total_price).await?;
-            total_price = new_total_price;
-            total_discount += promo_discount;
+        let mut summary = price_items(selected_items, &currency, &rates)?;
+        if let Some(code) = &promo_code {
+            summary.apply_promo_code(code, &app_state.promocodes_store).await?;
         }
 
-        if total_price < 0.0 {
-            total_price = 0.0;
-        }
+        let total_price = summary.total.clone();
+        let total_discount = summary.total_discount.clone();
+        let items = summary.into_items();
+
+        self.products_store.reserve_stock(&stock_items).await?;
 
         let order = Order {
             order_id: Uuid::new_v4(),
             user_id,
-            items: selected_items.into_iter().map(|item| item.product).collect(),
+            items,
             total_price,
             created_at: Utc::now().to_rfc3339(),
             discount: Some(total_discount),
@@ -187,12 +222,23 @@ impl OrdersStore {
             order_status: OrderStatus::Received,
         };
 
+        let recorded = order.clone();
+        let order_id = order.order_id;
         let mut orders = self.orders.lock().await;
         orders.push(order);
         drop(orders);
 
-        self.save().await?;
+        if let Err(e) = self.save().await {
+            self.orders.lock().await.retain(|o| o.order_id != order_id);
 
+            if let Err(restock_error) = self.products_store.restock(&stock_items).await {
+                log::error!("Failed to return reserved stock for order {}: {}", order_id, restock_error);
+            }
+
+            return Err(e);
+        }
+
+        self.recommender.record_order(&recorded).await;
         app_state.carts_store.remove_products_from_cart(user_id, selected_product_ids).await?;
 
         Ok(())
@@ -203,13 +249,29 @@ impl OrdersStore {
         orders.iter().filter(|o| o.user_id == user_id).cloned().collect()
     }
 
+    pub async fn has_ordered(&self, user_id: Uuid, product_id: Uuid) -> bool {
+        let orders = self.orders.lock().await;
+
+        orders
+            .iter()
+            .filter(|o| o.user_id == user_id && o.order_status != OrderStatus::Canceled)
+            .flat_map(|o| &o.items)
+            .any(|item| item.product.uuid == product_id || item.product.parent == Some(product_id))
+    }
+
     pub async fn remove_order(&self, user_id: Uuid, order_id: Uuid) -> Result<(), Box<dyn StdError>> {
         let mut orders = self.orders.lock().await;
 
         if let Some(pos) = orders.iter().position(|o| o.user_id == user_id && o.order_id == order_id) {
-            orders.remove(pos);
+            let order = orders.remove(pos);
             drop(orders);
             self.save().await?;
+
+            if order.order_status.is_cancelable() {
+                self.products_store.restock(&order.stock_items()).await?;
+                self.recommender.forget_order(&order).await;
+            }
+
             Ok(())
         } else {
             Err(Box::new(CustomError {
@@ -218,4 +280,57 @@ impl OrdersStore {
             }))
         }
     }
+
+    pub async fn cancel_order(&self, user_id: Uuid, order_id: Uuid) -> Result<(), Box<dyn StdError>> {
+        self.close_order(user_id, order_id, OrderStatus::Canceled).await
+    }
+
+    pub async fn return_order(&self, user_id: Uuid, order_id: Uuid) -> Result<(), Box<dyn StdError>> {
+        self.close_order(user_id, order_id, OrderStatus::Returned).await
+    }
+
+    async fn close_order(&self, user_id: Uuid, order_id: Uuid, status: OrderStatus) -> Result<(), Box<dyn StdError>> {
+        let mut orders = self.orders.lock().await;
+
+        let order = orders
+            .iter_mut()
+            .find(|o| o.user_id == user_id && o.order_id == order_id)
+            .ok_or_else(|| Box::new(CustomError::new("Order not found", "ORDER_NOT_FOUND")))?;
+
+        let allowed = match status {
+            OrderStatus::Canceled => order.order_status.is_cancelable(),
+            OrderStatus::Returned => order.order_status.is_returnable(),
+            _ => false,
+        };
+
+        if !allowed {
+            let (action, error_code) = match status {
+                OrderStatus::Canceled => ("canceled", "ORDER_CANNOT_BE_CANCELED"),
+                _ => ("returned", "ORDER_CANNOT_BE_RETURNED"),
+            };
+
+            return Err(Box::new(CustomError::new(
+                &format!("Order {} cannot be {} in its current status", order_id, action),
+                error_code,
+            )));
+        }
+
+        let previous_status = std::mem::replace(&mut order.order_status, status);
+        let closed = order.clone();
+        drop(orders);
+
+        if let Err(e) = self.save().await {
+            if let Some(order) = self.orders.lock().await.iter_mut().find(|o| o.order_id == order_id) {
+                order.order_status = previous_status;
+            }
+            return Err(e);
+        }
+
+        if status == OrderStatus::Canceled {
+            self.recommender.forget_order(&closed).await;
+        }
+
+        self.products_store.restock(&closed.stock_items()).await?;
+        Ok(())
+    }
 }
@@ -0,0 +1,210 @@
+use serde::Serialize;
+use std::error::Error as StdError;
+
+use crate::models::money::{Money, DEFAULT_CURRENCY};
+use crate::models::product::Product;
+use crate::utils::cart_store::ProductWithCount;
+use crate::utils::error::CustomError;
+use crate::utils::exchange_rates_store::ExchangeRates;
+use crate::utils::promo_codes_store::PromoCodesStore;
+
+#[derive(Serialize)]
+pub struct PricedLine {
+    pub product: Product,
+    pub count: u32,
+    pub unit_price: Money,
+    pub unit_discount: Money,
+    pub discounted_unit_price: Money,
+    pub line_discount: Money,
+    pub line_total: Money,
+}
+
+#[derive(Serialize)]
+pub struct PromoPreview {
+    pub code: String,
+    pub applied: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discount: Option<Money>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct PriceSummary {
+    pub currency: String,
+    pub lines: Vec<PricedLine>,
+    pub item_count: u32,
+    pub item_discount: Money,
+    pub subtotal: Money,
+    pub promo: Option<PromoPreview>,
+    pub total_discount: Money,
+    pub total: Money,
+}
+
+pub fn checkout_currency(preferred: Option<&str>, items: &[ProductWithCount]) -> String {
+    preferred
+        .map(str::to_string)
+        .or_else(|| items.first().map(|item| item.product.price.currency.clone()))
+        .unwrap_or_else(|| DEFAULT_CURRENCY.to_string())
+}
+
+pub fn price_items(items: Vec<ProductWithCount>, currency: &str, rates: &ExchangeRates) -> Result<PriceSummary, CustomError> {
+    let mut lines = Vec::with_capacity(items.len());
+    let mut item_count: u32 = 0;
+    let mut item_discount = Money::zero(currency);
+    let mut subtotal = Money::zero(currency);
+
+    for item in items {
+        let mut product = item.product;
+        rates.convert_product(&mut product, currency)?;
+
+        let unit_discount = product.discount_amount();
+        let discounted_unit_price = product.price.checked_sub(&unit_discount)?;
+        let line_discount = unit_discount.multiply(item.count)?;
+        let line_total = discounted_unit_price.multiply(item.count)?;
+
+        item_count = item_count.saturating_add(item.count);
+        item_discount = item_discount.checked_add(&line_discount)?;
+        subtotal = subtotal.checked_add(&line_total)?;
+
+        lines.push(PricedLine {
+            unit_price: product.price.clone(),
+            product,
+            count: item.count,
+            unit_discount,
+            discounted_unit_price,
+            line_discount,
+            line_total,
+        });
+    }
+
+    Ok(PriceSummary {
+        currency: currency.to_uppercase(),
+        lines,
+        item_count,
+        total_discount: item_discount.clone(),
+        item_discount,
+        total: subtotal.clone().non_negative(),
+        subtotal,
+        promo: None,
+    })
+}
+
+impl PriceSummary {
+    pub async fn apply_promo_code(&mut self, code: &str, promo_codes: &PromoCodesStore) -> Result<(), Box<dyn StdError>> {
+        let (total, discount) = promo_codes.apply_promo_code(code, &self.subtotal).await?;
+
+        self.total = total;
+        self.total_discount = self.item_discount.checked_add(&discount)?;
+        self.promo = Some(PromoPreview {
+            code: code.to_string(),
+            applied: true,
+            discount: Some(discount),
+            error_code: None,
+            message: None,
+        });
+        Ok(())
+    }
+
+    pub async fn preview_promo_code(&mut self, code: &str, promo_codes: &PromoCodesStore) -> Result<(), Box<dyn StdError>> {
+        match self.apply_promo_code(code, promo_codes).await {
+            Ok(()) => Ok(()),
+            Err(e) => match e.downcast_ref::<CustomError>() {
+                Some(error) => {
+                    self.promo = Some(PromoPreview {
+                        code: code.to_string(),
+                        applied: false,
+                        discount: None,
+                        error_code: Some(error.error_code.clone()),
+                        message: Some(error.message.clone()),
+                    });
+                    Ok(())
+                }
+                None => Err(e),
+            },
+        }
+    }
+
+    pub fn into_items(self) -> Vec<ProductWithCount> {
+        self.lines
+            .into_iter()
+            .map(|line| ProductWithCount { product: line.product, count: line.count })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn item(price: &str, discount: Option<f64>, count: u32) -> ProductWithCount {
+        let mut product = Product::test_fixture("Product", price);
+        product.discount = discount;
+        ProductWithCount { product, count }
+    }
+
+    async fn promo_codes() -> PromoCodesStore {
+        let path = std::env::temp_dir().join(format!("promocodes-{}.json", Uuid::new_v4()));
+        let promo_codes = json!([{
+            "code": "SAVE15",
+            "discount": 15,
+            "available_at": (Utc::now() - Duration::days(1)).to_rfc3339(),
+            "expired_at": (Utc::now() + Duration::days(1)).to_rfc3339()
+        }]);
+        std::fs::write(&path, promo_codes.to_string()).unwrap();
+
+        let store = PromoCodesStore::new(path.to_string_lossy().to_string()).await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+        store
+    }
+
+    fn cart() -> Vec<ProductWithCount> {
+        vec![item("1000", Some(10.0), 2), item("333.33", None, 1)]
+    }
+
+    #[tokio::test]
+    async fn summary_and_checkout_price_the_cart_identically() {
+        let promo_codes = promo_codes().await;
+        let rates = ExchangeRates::default();
+
+        let mut summary = price_items(cart(), "RUB", &rates).unwrap();
+        summary.preview_promo_code("SAVE15", &promo_codes).await.unwrap();
+
+        let mut checkout = price_items(cart(), "RUB", &rates).unwrap();
+        checkout.apply_promo_code("SAVE15", &promo_codes).await.unwrap();
+
+        assert_eq!(summary.item_count, 3);
+        assert_eq!(summary.lines[0].line_discount.amount, 20_000);
+        assert_eq!(summary.item_discount.amount, 20_000);
+        assert_eq!(summary.subtotal.amount, 213_333);
+        assert_eq!(summary.promo.as_ref().and_then(|p| p.discount.clone()).unwrap().amount, 32_000);
+        assert_eq!(summary.total_discount.amount, 52_000);
+        assert_eq!(summary.total.amount, 181_333);
+
+        assert_eq!(checkout.total, summary.total);
+        assert_eq!(checkout.total_discount, summary.total_discount);
+        assert_eq!(checkout.subtotal, summary.subtotal);
+    }
+
+    #[tokio::test]
+    async fn invalid_promo_codes_fail_checkout_but_only_annotate_the_summary() {
+        let promo_codes = promo_codes().await;
+        let rates = ExchangeRates::default();
+
+        let mut summary = price_items(cart(), "RUB", &rates).unwrap();
+        summary.preview_promo_code("UNKNOWN", &promo_codes).await.unwrap();
+
+        let promo = summary.promo.as_ref().unwrap();
+        assert!(!promo.applied);
+        assert_eq!(promo.error_code.as_deref(), Some("PROMO_CODE_NOT_FOUND"));
+        assert_eq!(summary.total, summary.subtotal);
+
+        let mut checkout = price_items(cart(), "RUB", &rates).unwrap();
+        assert!(checkout.apply_promo_code("UNKNOWN", &promo_codes).await.is_err());
+    }
+}
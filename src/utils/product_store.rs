@@ -0,0 +1,1025 @@
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::error::Error as StdError;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+use log::info;
+
+use crate::models::money::{Money, DEFAULT_CURRENCY};
+use crate::models::product::{Product, ProductDraft, ProductFilter, ProductPatch, ProductSort};
+use crate::utils::catalog_io::{export_catalog, CatalogFormat, ImportReport, ParsedCatalog, RowError};
+use crate::utils::categories_store::Breadcrumb;
+use crate::utils::error::CustomError;
+use crate::utils::file_watcher::ReloadDiff;
+use crate::utils::json_file::JsonFile;
+use crate::utils::search_index::{highlight, snippet, SearchIndex, SuggestIndex, SuggestionKind};
+
+const SNIPPET_LENGTH: usize = 160;
+const PRICE_BUCKETS: [f64; 6] = [0.0, 5_000.0, 10_000.0, 25_000.0, 50_000.0, 100_000.0];
+const RATING_BANDS: [f64; 4] = [4.0, 3.0, 2.0, 1.0];
+
+#[derive(Serialize, Clone)]
+pub struct ProductView {
+    #[serde(flatten)]
+    pub product: Product,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<Product>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selected_variant: Option<Uuid>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub breadcrumbs: Vec<Breadcrumb>,
+}
+
+impl ProductView {
+    pub fn products_mut(&mut self) -> impl Iterator<Item = &mut Product> {
+        std::iter::once(&mut self.product).chain(self.variants.iter_mut())
+    }
+}
+
+#[derive(Serialize)]
+pub struct ProductPage {
+    pub items: Vec<ProductView>,
+    pub page: usize,
+    pub limit: usize,
+    pub total: usize,
+    pub total_pages: usize,
+}
+
+#[derive(Serialize)]
+pub struct SearchHighlights {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct SearchResult {
+    pub product: ProductView,
+    pub score: f32,
+    pub highlights: SearchHighlights,
+}
+
+#[derive(Serialize)]
+pub struct SearchPage {
+    pub query: String,
+    pub items: Vec<SearchResult>,
+    pub page: usize,
+    pub limit: usize,
+    pub total: usize,
+    pub total_pages: usize,
+}
+
+#[derive(Serialize)]
+pub struct ProductSuggestion {
+    pub uuid: Uuid,
+    pub pathurl: String,
+    pub name: String,
+}
+
+#[derive(Serialize)]
+pub struct Suggestions {
+    pub query: String,
+    pub products: Vec<ProductSuggestion>,
+    pub brands: Vec<String>,
+    pub tags: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: usize,
+}
+
+#[derive(Serialize)]
+pub struct PriceBucket {
+    pub from: f64,
+    pub to: Option<f64>,
+    pub count: usize,
+}
+
+#[derive(Serialize)]
+pub struct RatingBand {
+    pub min_rating: f64,
+    pub count: usize,
+}
+
+#[derive(Serialize)]
+pub struct Facets {
+    pub total: usize,
+    pub brands: Vec<FacetCount>,
+    pub tags: Vec<FacetCount>,
+    pub price_buckets: Vec<PriceBucket>,
+    pub rating_bands: Vec<RatingBand>,
+}
+
+#[derive(Serialize)]
+pub struct BulkUpsertResult {
+    pub created: usize,
+    pub updated: usize,
+    pub total: usize,
+}
+
+pub struct ReviewStats {
+    pub rating: f64,
+    pub count: u32,
+}
+
+impl ReviewStats {
+    pub fn new(sum: u32, count: u32) -> Self {
+        let rating = if count == 0 {
+            0.0
+        } else {
+            (sum as f64 / count as f64 * 10.0).round() / 10.0
+        };
+
+        ReviewStats { rating, count }
+    }
+}
+
+#[derive(Default)]
+pub struct ProductCatalog {
+    pub products: Vec<Product>,
+    by_uuid: HashMap<Uuid, usize>,
+    by_article: HashMap<String, usize>,
+    by_pathurl: HashMap<String, usize>,
+    by_brand: HashMap<String, Vec<usize>>,
+    by_tag: HashMap<String, Vec<usize>>,
+    variants: HashMap<Uuid, Vec<usize>>,
+    search_index: SearchIndex,
+    suggest_index: SuggestIndex,
+}
+
+impl ProductCatalog {
+    pub fn new(products: Vec<Product>) -> Result<Self, CustomError> {
+        let mut catalog = ProductCatalog::default();
+
+        for (pos, product) in products.iter().enumerate() {
+            product.validate()?;
+
+            if catalog.by_uuid.insert(product.uuid, pos).is_some() {
+                return Err(CustomError::new(
+                    &format!("Duplicate product uuid `{}`", product.uuid),
+                    "DUPLICATE_PRODUCT_UUID",
+                ));
+            }
+
+            if catalog.by_article.insert(product.article.clone(), pos).is_some() {
+                return Err(CustomError::new(
+                    &format!("Duplicate product article `{}`", product.article),
+                    "DUPLICATE_PRODUCT_ARTICLE",
+                ));
+            }
+
+            if catalog.by_pathurl.insert(normalize_pathurl(&product.pathurl), pos).is_some() {
+                return Err(CustomError::new(
+                    &format!("Duplicate product pathurl `{}`", product.pathurl),
+                    "DUPLICATE_PRODUCT_PATHURL",
+                ));
+            }
+
+            catalog.by_brand.entry(product.brand.to_lowercase()).or_default().push(pos);
+
+            for tag in &product.tags {
+                catalog.by_tag.entry(tag.to_lowercase()).or_default().push(pos);
+            }
+        }
+
+        for (pos, product) in products.iter().enumerate() {
+            let Some(parent_uuid) = product.parent else {
+                continue;
+            };
+
+            let parent = catalog.by_uuid.get(&parent_uuid).map(|&p| &products[p]).ok_or_else(|| {
+                CustomError::new(
+                    &format!("Variant {} references a missing parent {}", product.uuid, parent_uuid),
+                    "INVALID_PRODUCT_VARIANT",
+                )
+            })?;
+
+            if parent.is_variant() {
+                return Err(CustomError::new(
+                    &format!("Variant {} cannot use another variant {} as its parent", product.uuid, parent_uuid),
+                    "INVALID_PRODUCT_VARIANT",
+                ));
+            }
+
+            let axes: BTreeSet<&String> = parent.variant_axes.iter().collect();
+            let keys: BTreeSet<&String> = product.options.keys().collect();
+
+            if axes.is_empty() || axes != keys || product.options.values().any(|v| v.trim().is_empty()) {
+                return Err(CustomError::new(
+                    &format!(
+                        "Variant {} must set exactly the options [{}] of its parent",
+                        product.uuid,
+                        parent.variant_axes.join(", ")
+                    ),
+                    "INVALID_PRODUCT_VARIANT",
+                ));
+            }
+
+            catalog.variants.entry(parent_uuid).or_default().push(pos);
+        }
+
+        for positions in catalog.variants.values() {
+            let mut seen = HashSet::new();
+
+            if let Some(&pos) = positions.iter().find(|&&pos| !seen.insert(&products[pos].options)) {
+                return Err(CustomError::new(
+                    &format!("Variant {} duplicates the options of a sibling variant", products[pos].uuid),
+                    "DUPLICATE_PRODUCT_VARIANT",
+                ));
+            }
+        }
+
+        catalog.search_index = SearchIndex::build(&products);
+        catalog.suggest_index = SuggestIndex::build(&products);
+        catalog.products = products;
+        Ok(catalog)
+    }
+
+    pub fn get(&self, uuid: Uuid) -> Option<&Product> {
+        self.by_uuid.get(&uuid).map(|&pos| &self.products[pos])
+    }
+
+    pub fn has_variants(&self, uuid: Uuid) -> bool {
+        self.variants.contains_key(&uuid)
+    }
+
+    fn group(&self, pos: usize) -> usize {
+        match self.products[pos].parent {
+            Some(parent) => self.by_uuid[&parent],
+            None => pos,
+        }
+    }
+
+    fn variants_of(&self, pos: usize) -> impl Iterator<Item = Product> + '_ {
+        let parent = &self.products[pos];
+
+        self.variants
+            .get(&parent.uuid)
+            .into_iter()
+            .flatten()
+            .map(move |&variant| inherit_from_parent(self.products[variant].clone(), parent))
+    }
+
+    fn resolved(&self, pos: usize) -> Product {
+        let group = self.group(pos);
+
+        if group == pos {
+            self.products[pos].clone()
+        } else {
+            inherit_from_parent(self.products[pos].clone(), &self.products[group])
+        }
+    }
+
+    fn group_matches(&self, pos: usize, filter: &ProductFilter) -> bool {
+        filter.matches(&self.products[pos]) || self.variants_of(pos).any(|v| filter.matches(&v))
+    }
+
+    fn view(&self, pos: usize) -> ProductView {
+        let group = self.group(pos);
+
+        ProductView {
+            product: self.products[group].clone(),
+            variants: self.variants_of(group).collect(),
+            selected_variant: (group != pos).then_some(self.products[pos].uuid),
+            breadcrumbs: Vec::new(),
+        }
+    }
+
+    fn get_mut(&mut self, uuid: Uuid) -> Option<&mut Product> {
+        self.by_uuid.get(&uuid).map(|&pos| &mut self.products[pos])
+    }
+
+    fn candidates(&self, filter: &ProductFilter) -> Vec<usize> {
+        let brands = filter.brands();
+        let tags = filter.tag_list();
+
+        if brands.is_empty() && tags.is_empty() {
+            return (0..self.products.len()).collect();
+        }
+
+        let lookup = |index: &HashMap<String, Vec<usize>>, keys: &[String]| -> BTreeSet<usize> {
+            keys.iter()
+                .filter_map(|k| index.get(k))
+                .flatten()
+                .copied()
+                .collect()
+        };
+
+        let by_brand = lookup(&self.by_brand, &brands);
+        let by_tag = lookup(&self.by_tag, &tags);
+
+        match (brands.is_empty(), tags.is_empty()) {
+            (false, true) => by_brand.into_iter().collect(),
+            (true, false) => by_tag.into_iter().collect(),
+            _ => by_brand.intersection(&by_tag).copied().collect(),
+        }
+    }
+
+    pub fn filter(&self, filter: &ProductFilter) -> Vec<usize> {
+        let groups: BTreeSet<usize> = self.candidates(filter)
+            .into_iter()
+            .map(|pos| self.group(pos))
+            .collect();
+
+        groups
+            .into_iter()
+            .filter(|&pos| self.group_matches(pos, filter))
+            .collect()
+    }
+}
+
+pub struct ProductStore {
+    pub catalog: Mutex<ProductCatalog>,
+    pub file: JsonFile,
+    pub low_stock_threshold: u32,
+}
+
+impl ProductStore {
+    pub async fn new(products_file_path: String, low_stock_threshold: u32) -> Result<Self, Box<dyn StdError>> {
+        let (file, raw): (JsonFile, serde_json::Value) = JsonFile::open(products_file_path, "[]").await?;
+        let has_legacy_prices = raw
+            .as_array()
+            .is_some_and(|items| items.iter().any(|p| !p["price"].is_object()));
+
+        let products: Vec<Product> = serde_json::from_value(raw)?;
+        let catalog = ProductCatalog::new(products)?;
+
+        info!("Product catalog loaded from {}: {} products", file.path, catalog.products.len());
+
+        let store = ProductStore {
+            catalog: Mutex::new(catalog),
+            file,
+            low_stock_threshold,
+        };
+
+        if has_legacy_prices {
+            info!("Migrating legacy product prices in {}", store.file.path);
+            store.save().await?;
+        }
+
+        Ok(store)
+    }
+
+    pub async fn save(&self) -> Result<(), Box<dyn StdError>> {
+        let catalog = self.catalog.lock().await;
+        self.write_products(&catalog.products).await
+    }
+
+    async fn write_products(&self, products: &[Product]) -> Result<(), Box<dyn StdError>> {
+        self.file.write(products).await?;
+        info!("Products successfully saved.");
+        Ok(())
+    }
+
+    async fn update_catalog<T, F>(&self, update: F) -> Result<T, Box<dyn StdError>>
+    where
+        F: FnOnce(&mut Vec<Product>) -> Result<T, CustomError>,
+    {
+        let mut catalog = self.catalog.lock().await;
+        let mut products = catalog.products.clone();
+
+        let result = update(&mut products)?;
+        let updated = ProductCatalog::new(products)?;
+
+        self.write_products(&updated.products).await?;
+        *catalog = updated;
+        Ok(result)
+    }
+
+    pub async fn create_product(&self, draft: ProductDraft) -> Result<Product, Box<dyn StdError>> {
+        let uuid = draft.uuid.unwrap_or_else(Uuid::new_v4);
+        let product = draft.into_product(uuid, None)?;
+
+        self.update_catalog(|products| {
+            products.push(product.clone());
+            Ok(product)
+        }).await
+    }
+
+    pub async fn update_product(&self, uuid: Uuid, patch: ProductPatch) -> Result<Product, Box<dyn StdError>> {
+        self.update_catalog(|products| {
+            let product = products
+                .iter_mut()
+                .find(|p| p.uuid == uuid)
+                .ok_or_else(|| CustomError::new("Product not found", "PRODUCT_NOT_FOUND"))?;
+
+            patch.apply(product)?;
+            Ok(product.clone())
+        }).await
+    }
+
+    pub async fn delete_product(&self, uuid: Uuid) -> Result<(), Box<dyn StdError>> {
+        self.update_catalog(|products| {
+            let pos = products
+                .iter()
+                .position(|p| p.uuid == uuid)
+                .ok_or_else(|| CustomError::new("Product not found", "PRODUCT_NOT_FOUND"))?;
+
+            products.remove(pos);
+            Ok(())
+        }).await
+    }
+
+    pub async fn upsert_products(&self, drafts: Vec<ProductDraft>) -> Result<BulkUpsertResult, Box<dyn StdError>> {
+        self.update_catalog(|products| {
+            let mut result = BulkUpsertResult { created: 0, updated: 0, total: drafts.len() };
+
+            for draft in drafts {
+                if upsert_draft(products, draft)? {
+                    result.created += 1;
+                } else {
+                    result.updated += 1;
+                }
+            }
+
+            Ok(result)
+        }).await
+    }
+
+    pub async fn import_products(&self, parsed: ParsedCatalog, dry_run: bool) -> Result<ImportReport, Box<dyn StdError>> {
+        let mut catalog = self.catalog.lock().await;
+        let mut products = catalog.products.clone();
+
+        let mut report = ImportReport {
+            dry_run,
+            rows: parsed.rows.len() + parsed.errors.len(),
+            created: 0,
+            updated: 0,
+            errors: parsed.errors,
+        };
+
+        for (row, draft) in parsed.rows {
+            match upsert_draft(&mut products, draft) {
+                Ok(true) => report.created += 1,
+                Ok(false) => report.updated += 1,
+                Err(e) => report.errors.push(RowError { row, message: e.to_string() }),
+            }
+        }
+
+        report.errors.sort_by_key(|e| e.row);
+
+        if dry_run || !report.errors.is_empty() {
+            return Ok(report);
+        }
+
+        let updated = ProductCatalog::new(products)?;
+        self.write_products(&updated.products).await?;
+        *catalog = updated;
+        Ok(report)
+    }
+
+    pub async fn export_products(&self, format: CatalogFormat) -> Result<String, Box<dyn StdError>> {
+        let catalog = self.catalog.lock().await;
+        export_catalog(format, &catalog.products)
+    }
+
+    pub async fn reload(&self) -> Result<Option<ReloadDiff>, Box<dyn StdError>> {
+        let mut catalog = self.catalog.lock().await;
+
+        self.file.reload(|products: Vec<Product>| {
+            let reloaded = ProductCatalog::new(products)?;
+            let diff = ReloadDiff::between(&catalog.products, &reloaded.products, |p| p.uuid);
+
+            *catalog = reloaded;
+            Ok(diff)
+        }).await
+    }
+
+    pub async fn get_product(&self, uuid: Uuid) -> Option<Product> {
+        let catalog = self.catalog.lock().await;
+        catalog.get(uuid).cloned()
+    }
+
+    pub async fn get_sellable_product(&self, uuid: Uuid) -> Result<Product, CustomError> {
+        let catalog = self.catalog.lock().await;
+
+        let &pos = catalog.by_uuid.get(&uuid).ok_or_else(|| {
+            CustomError::new(&format!("Product {} is no longer available", uuid), "PRODUCT_NOT_FOUND")
+        })?;
+
+        if catalog.has_variants(uuid) {
+            return Err(CustomError::new(
+                &format!("Choose one of the variants of {}", catalog.products[pos].name),
+                "VARIANT_REQUIRED",
+            ));
+        }
+
+        Ok(catalog.resolved(pos))
+    }
+
+    pub async fn get_product_view(&self, uuid: Uuid) -> Option<ProductView> {
+        let catalog = self.catalog.lock().await;
+        catalog.by_uuid.get(&uuid).map(|&pos| catalog.view(pos))
+    }
+
+    pub async fn find_by_article(&self, article: &str) -> Option<ProductView> {
+        let catalog = self.catalog.lock().await;
+        catalog.by_article.get(article).map(|&pos| catalog.view(pos))
+    }
+
+    pub async fn find_by_pathurl(&self, pathurl: &str) -> Option<ProductView> {
+        let catalog = self.catalog.lock().await;
+        catalog.by_pathurl.get(&normalize_pathurl(pathurl)).map(|&pos| catalog.view(pos))
+    }
+
+    pub async fn reserve_stock(&self, items: &[(Uuid, u32)]) -> Result<(), Box<dyn StdError>> {
+        let mut catalog = self.catalog.lock().await;
+
+        for &(uuid, count) in items {
+            let product = catalog.get(uuid).ok_or_else(|| {
+                CustomError::new(&format!("Product {} is no longer available", uuid), "PRODUCT_NOT_FOUND")
+            })?;
+            product.ensure_available(count)?;
+        }
+
+        for &(uuid, count) in items {
+            if let Some(stock) = catalog.get_mut(uuid).and_then(|p| p.stock.as_mut()) {
+                *stock -= count;
+            }
+        }
+
+        drop(catalog);
+        self.save().await
+    }
+
+    pub async fn restock(&self, items: &[(Uuid, u32)]) -> Result<(), Box<dyn StdError>> {
+        let mut catalog = self.catalog.lock().await;
+
+        for &(uuid, count) in items {
+            if let Some(stock) = catalog.get_mut(uuid).and_then(|p| p.stock.as_mut()) {
+                *stock = stock.saturating_add(count);
+            }
+        }
+
+        drop(catalog);
+        self.save().await
+    }
+
+    /// Only products listed in `stats` are updated; the rest keep the rating and review count from the catalog.
+    pub async fn apply_review_stats(&self, stats: &HashMap<Uuid, ReviewStats>) -> Result<(), Box<dyn StdError>> {
+        let mut catalog = self.catalog.lock().await;
+        let mut changed = false;
+
+        for (&uuid, stats) in stats {
+            if let Some(product) = catalog.get_mut(uuid) {
+                if product.rating != stats.rating || product.reviews != stats.count as f64 {
+                    product.rating = stats.rating;
+                    product.reviews = stats.count as f64;
+                    changed = true;
+                }
+            }
+        }
+
+        drop(catalog);
+
+        if changed {
+            self.save().await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn similarity_scores(&self, seeds: &[(Uuid, f64)], exclude: &HashSet<Uuid>) -> HashMap<Uuid, f64> {
+        let catalog = self.catalog.lock().await;
+
+        let seeds: Vec<(&Product, f64)> = seeds
+            .iter()
+            .filter_map(|&(uuid, weight)| catalog.get(uuid).map(|product| (product, weight)))
+            .collect();
+
+        catalog.products
+            .iter()
+            .filter(|product| product.parent.is_none() && !exclude.contains(&product.uuid))
+            .map(|product| {
+                let score = seeds.iter().map(|(seed, weight)| similarity(seed, product) * weight).sum();
+                (product.uuid, score)
+            })
+            .collect()
+    }
+
+    pub async fn popular_products(&self, exclude: &HashSet<Uuid>, limit: usize) -> Vec<Uuid> {
+        let catalog = self.catalog.lock().await;
+
+        let mut products: Vec<&Product> = catalog.products
+            .iter()
+            .filter(|product| product.parent.is_none() && !exclude.contains(&product.uuid))
+            .collect();
+
+        products.sort_by(|a, b| b.reviews.total_cmp(&a.reviews).then(b.rating.total_cmp(&a.rating)));
+        products.into_iter().take(limit).map(|product| product.uuid).collect()
+    }
+
+    pub async fn product_views(&self, uuids: &[Uuid], exclude: &HashSet<Uuid>) -> Vec<ProductView> {
+        let catalog = self.catalog.lock().await;
+
+        uuids
+            .iter()
+            .filter(|uuid| !exclude.contains(uuid))
+            .filter_map(|uuid| catalog.by_uuid.get(uuid))
+            .map(|&pos| catalog.view(pos))
+            .collect()
+    }
+
+    pub async fn resolved_products(&self, uuids: &[Uuid]) -> Vec<Product> {
+        let catalog = self.catalog.lock().await;
+
+        uuids
+            .iter()
+            .filter_map(|uuid| catalog.by_uuid.get(uuid))
+            .map(|&pos| catalog.resolved(pos))
+            .collect()
+    }
+
+    pub async fn group_tags(&self) -> Vec<BTreeSet<String>> {
+        let catalog = self.catalog.lock().await;
+
+        catalog.products
+            .iter()
+            .enumerate()
+            .filter(|(_, product)| product.parent.is_none())
+            .map(|(pos, product)| {
+                product.tags
+                    .iter()
+                    .cloned()
+                    .chain(catalog.variants_of(pos).flat_map(|variant| variant.tags))
+                    .map(|tag| tag.to_lowercase())
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub async fn low_stock(&self, threshold: u32) -> Vec<Product> {
+        let catalog = self.catalog.lock().await;
+
+        let mut products: Vec<Product> = catalog.products
+            .iter()
+            .filter(|p| p.stock.is_some_and(|stock| stock <= threshold) && !catalog.has_variants(p.uuid))
+            .cloned()
+            .collect();
+
+        products.sort_by_key(|p| p.stock);
+        products
+    }
+
+    pub async fn query_products(
+        &self,
+        filter: &ProductFilter,
+        sort: Option<ProductSort>,
+        page: usize,
+        limit: usize,
+    ) -> ProductPage {
+        let catalog = self.catalog.lock().await;
+
+        let mut matched: Vec<(usize, &Product)> = catalog
+            .filter(filter)
+            .into_iter()
+            .map(|pos| (pos, &catalog.products[pos]))
+            .collect();
+
+        if let Some(sort) = sort {
+            matched.sort_by(|(a_pos, a), (b_pos, b)| compare_products(sort, filter, a, *a_pos, b, *b_pos));
+        }
+
+        let page = page.max(1);
+        let limit = limit.max(1);
+        let total = matched.len();
+        let total_pages = total.div_ceil(limit);
+
+        let items = matched
+            .into_iter()
+            .skip((page - 1) * limit)
+            .take(limit)
+            .map(|(pos, _)| catalog.view(pos))
+            .collect();
+
+        ProductPage {
+            items,
+            page,
+            limit,
+            total,
+            total_pages,
+        }
+    }
+
+    pub async fn search_products(
+        &self,
+        query: &str,
+        filter: &ProductFilter,
+        page: usize,
+        limit: usize,
+    ) -> SearchPage {
+        let catalog = self.catalog.lock().await;
+
+        let mut seen = HashSet::new();
+        let hits: Vec<_> = catalog
+            .search_index
+            .search(query)
+            .into_iter()
+            .filter(|hit| {
+                let group = catalog.group(hit.doc);
+                seen.insert(group) && catalog.group_matches(group, filter)
+            })
+            .collect();
+
+        let page = page.max(1);
+        let limit = limit.max(1);
+        let total = hits.len();
+        let total_pages = total.div_ceil(limit);
+
+        let items = hits
+            .into_iter()
+            .skip((page - 1) * limit)
+            .take(limit)
+            .map(|hit| {
+                let matched = &catalog.products[hit.doc];
+                let highlights = SearchHighlights {
+                    name: highlight(&matched.name, &hit.terms),
+                    description: matched
+                        .description
+                        .as_deref()
+                        .and_then(|d| snippet(d, &hit.terms, SNIPPET_LENGTH)),
+                };
+
+                SearchResult {
+                    product: catalog.view(hit.doc),
+                    score: hit.score,
+                    highlights,
+                }
+            })
+            .collect();
+
+        SearchPage {
+            query: query.to_string(),
+            items,
+            page,
+            limit,
+            total,
+            total_pages,
+        }
+    }
+
+    pub async fn facets(&self, query: Option<&str>, filter: &ProductFilter) -> Facets {
+        let catalog = self.catalog.lock().await;
+
+        let base: BTreeSet<usize> = match query {
+            Some(q) => catalog
+                .search_index
+                .search(q)
+                .into_iter()
+                .map(|hit| catalog.group(hit.doc))
+                .collect(),
+            None => (0..catalog.products.len()).map(|pos| catalog.group(pos)).collect(),
+        };
+
+        let matching = |filter: &ProductFilter| -> Vec<&Product> {
+            base.iter()
+                .filter(|&&pos| catalog.group_matches(pos, filter))
+                .map(|&pos| &catalog.products[pos])
+                .collect()
+        };
+
+        let total = matching(filter).len();
+
+        let brands = count_values(
+            matching(&ProductFilter { brand: None, ..filter.clone() })
+                .into_iter()
+                .map(|p| p.brand.clone()),
+        );
+
+        let tags = count_values(
+            matching(&ProductFilter { tags: None, ..filter.clone() })
+                .into_iter()
+                .flat_map(|p| p.tags.clone()),
+        );
+
+        let priced: Vec<f64> = matching(&ProductFilter { min_price: None, max_price: None, ..filter.clone() })
+            .into_iter()
+            .filter_map(|p| filter.filter_price(p))
+            .collect();
+
+        let edges = price_bucket_edges(filter);
+        let price_buckets = edges
+            .iter()
+            .enumerate()
+            .map(|(i, &from)| {
+                let to = edges.get(i + 1).copied();
+                PriceBucket {
+                    from,
+                    to,
+                    count: priced.iter().filter(|&&p| p >= from && to.is_none_or(|to| p < to)).count(),
+                }
+            })
+            .collect();
+
+        let rated = matching(&ProductFilter { min_rating: None, ..filter.clone() });
+        let rating_bands = RATING_BANDS
+            .iter()
+            .map(|&min_rating| RatingBand {
+                min_rating,
+                count: rated.iter().filter(|p| p.rating >= min_rating).count(),
+            })
+            .collect();
+
+        Facets {
+            total,
+            brands,
+            tags,
+            price_buckets,
+            rating_bands,
+        }
+    }
+
+    pub async fn suggest(&self, query: &str, limit: usize) -> Suggestions {
+        let catalog = self.catalog.lock().await;
+        let index = &catalog.suggest_index;
+
+        let mut seen = HashSet::new();
+        let products = index
+            .suggest(query, SuggestionKind::Product, limit)
+            .into_iter()
+            .filter_map(|m| m.doc)
+            .map(|doc| catalog.group(doc))
+            .filter(|&group| seen.insert(group))
+            .map(|group| {
+                let product = &catalog.products[group];
+                ProductSuggestion {
+                    uuid: product.uuid,
+                    pathurl: product.pathurl.clone(),
+                    name: product.name.clone(),
+                }
+            })
+            .collect();
+
+        let texts = |kind| {
+            index
+                .suggest(query, kind, limit)
+                .into_iter()
+                .map(|m| m.text.to_string())
+                .collect()
+        };
+
+        Suggestions {
+            query: query.to_string(),
+            products,
+            brands: texts(SuggestionKind::Brand),
+            tags: texts(SuggestionKind::Tag),
+        }
+    }
+}
+
+fn count_values(values: impl Iterator<Item = String>) -> Vec<FacetCount> {
+    let mut counts: HashMap<String, FacetCount> = HashMap::new();
+
+    for value in values {
+        counts
+            .entry(value.to_lowercase())
+            .or_insert_with(|| FacetCount { value, count: 0 })
+            .count += 1;
+    }
+
+    let mut counts: Vec<FacetCount> = counts.into_values().collect();
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+    counts
+}
+
+fn upsert_draft(products: &mut Vec<Product>, draft: ProductDraft) -> Result<bool, CustomError> {
+    let existing = products.iter().position(|p| match draft.uuid {
+        Some(uuid) => p.uuid == uuid,
+        None => p.article == draft.article.trim(),
+    });
+
+    let uuid = match existing {
+        Some(pos) => products[pos].uuid,
+        None => draft.uuid.unwrap_or_else(Uuid::new_v4),
+    };
+    let product = draft.into_product(uuid, existing.map(|pos| &products[pos]))?;
+    let pathurl = normalize_pathurl(&product.pathurl);
+
+    let conflict = products.iter().enumerate().find(|(pos, p)| {
+        Some(*pos) != existing && (p.article == product.article || normalize_pathurl(&p.pathurl) == pathurl)
+    });
+
+    if let Some((_, other)) = conflict {
+        return Err(CustomError::new(
+            &format!("Product {} conflicts with {} on article or pathurl", product.article, other.uuid),
+            "DUPLICATE_PRODUCT",
+        ));
+    }
+
+    match existing {
+        Some(pos) => {
+            products[pos] = product;
+            Ok(false)
+        }
+        None => {
+            products.push(product);
+            Ok(true)
+        }
+    }
+}
+
+fn inherit_from_parent(mut variant: Product, parent: &Product) -> Product {
+    if variant.brand.is_empty() {
+        variant.brand = parent.brand.clone();
+    }
+    if variant.tags.is_empty() {
+        variant.tags = parent.tags.clone();
+    }
+    if variant.image.is_empty() {
+        variant.image = parent.image.clone();
+    }
+    if variant.description.is_none() {
+        variant.description = parent.description.clone();
+    }
+    variant
+}
+
+fn similarity(a: &Product, b: &Product) -> f64 {
+    let tags: HashSet<String> = a.tags.iter().map(|tag| tag.to_lowercase()).collect();
+    let shared_tags = b.tags.iter().filter(|tag| tags.contains(&tag.to_lowercase())).count();
+    let same_brand = !a.brand.is_empty() && a.brand.to_lowercase() == b.brand.to_lowercase();
+
+    shared_tags as f64 * 2.0 + if same_brand { 1.0 } else { 0.0 }
+}
+
+fn normalize_pathurl(pathurl: &str) -> String {
+    format!("/{}", pathurl.trim_matches('/'))
+}
+
+/// `PRICE_BUCKETS` are in the default catalog currency; they are converted to the display currency
+/// and rounded to whole units so the buckets stay meaningful for every currency.
+fn price_bucket_edges(filter: &ProductFilter) -> Vec<f64> {
+    let Some((currency, rates)) = &filter.price_currency else {
+        return PRICE_BUCKETS.to_vec();
+    };
+
+    let edges: Option<Vec<f64>> = PRICE_BUCKETS
+        .iter()
+        .map(|&edge| rates.convert(&Money::from_major(edge, DEFAULT_CURRENCY), currency).ok())
+        .map(|edge| edge.map(|edge| edge.to_major().round()))
+        .collect();
+
+    match edges {
+        Some(mut edges) => {
+            edges.dedup();
+            edges
+        }
+        None => PRICE_BUCKETS.to_vec(),
+    }
+}
+
+/// Price sorts use the same converted price as the price filters; prices that cannot be converted sort last.
+fn compare_products(sort: ProductSort, filter: &ProductFilter, a: &Product, a_pos: usize, b: &Product, b_pos: usize) -> Ordering {
+    let by_price = |descending: bool| match (filter.filter_price(a), filter.filter_price(b)) {
+        (Some(a), Some(b)) if descending => b.total_cmp(&a),
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        (a, b) => a.is_none().cmp(&b.is_none()),
+    };
+
+    match sort {
+        ProductSort::PriceAsc => by_price(false),
+        ProductSort::PriceDesc => by_price(true),
+        ProductSort::Rating => b.rating.total_cmp(&a.rating).then(b.reviews.total_cmp(&a.reviews)),
+        ProductSort::Reviews => b.reviews.total_cmp(&a.reviews),
+        ProductSort::Newest => b.is_new.cmp(&a.is_new).then(b_pos.cmp(&a_pos)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::exchange_rates_store::ExchangeRates;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn price_sort_uses_prices_converted_to_the_display_currency() {
+        let rub = Product::test_fixture("Рубли", "1000");
+        let mut usd = Product::test_fixture("Dollars", "20");
+        usd.price = Money::new(2_000, "USD");
+
+        let rates = ExchangeRates {
+            base: "RUB".to_string(),
+            rates: BTreeMap::from([("RUB".to_string(), 1.0), ("USD".to_string(), 0.01)]),
+            updated_at: None,
+        };
+        let filter = ProductFilter { price_currency: Some(("RUB".to_string(), rates)), ..Default::default() };
+
+        assert_eq!(compare_products(ProductSort::PriceAsc, &filter, &rub, 0, &usd, 1), Ordering::Less);
+        assert_eq!(compare_products(ProductSort::PriceDesc, &filter, &rub, 0, &usd, 1), Ordering::Greater);
+    }
+
+    #[test]
+    fn price_bucket_edges_are_converted_to_the_display_currency() {
+        let rates = ExchangeRates {
+            base: "RUB".to_string(),
+            rates: BTreeMap::from([("RUB".to_string(), 1.0), ("USD".to_string(), 0.01)]),
+            updated_at: None,
+        };
+        let filter = ProductFilter { price_currency: Some(("USD".to_string(), rates)), ..Default::default() };
+
+        assert_eq!(price_bucket_edges(&filter), vec![0.0, 50.0, 100.0, 250.0, 500.0, 1_000.0]);
+        assert_eq!(price_bucket_edges(&ProductFilter::default()), PRICE_BUCKETS.to_vec());
+    }
+}
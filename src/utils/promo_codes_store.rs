@@ -1,11 +1,12 @@
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::error::Error as StdError;
-use std::path::Path;
-use tokio::fs::{create_dir_all, File};
-use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::Mutex;
 
+use crate::models::money::Money;
 use crate::utils::error::CustomError;
+use crate::utils::file_watcher::ReloadDiff;
+use crate::utils::json_file::JsonFile;
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct PromoCode {
@@ -15,45 +16,40 @@ pub struct PromoCode {
     pub expired_at: String,
 }
 
-#[derive(Clone)]
 pub struct PromoCodesStore {
-    pub promocodes_file_path: String,
+    pub promocodes: Mutex<Vec<PromoCode>>,
+    pub file: JsonFile,
 }
 
 impl PromoCodesStore {
     pub async fn new(promocodes_file_path: String) -> Result<Self, Box<dyn StdError>> {
-        let path = Path::new(&promocodes_file_path);
+        let (file, promo_codes): (JsonFile, Vec<PromoCode>) = JsonFile::open(promocodes_file_path, "[]").await?;
+        validate_promo_codes(&promo_codes)?;
 
-        if let Some(parent) = path.parent() {
-            create_dir_all(parent).await.expect("Failed to create directories for promocodes.json file");
-        }
-
-        if !path.exists() {
-            let mut file = File::create(path).await.expect("Failed to create promocodes.json file");
-            file.write_all(b"[]").await.expect("Failed to write empty array to file");
-        }
+        Ok(PromoCodesStore {
+            promocodes: Mutex::new(promo_codes),
+            file,
+        })
+    }
 
-        let file = File::open(path).await.expect("Failed to open promocodes.json file");
-        let mut reader = BufReader::new(file);
-        let mut data = String::new();
-        reader.read_to_string(&mut data).await.expect("Failed to read file");
+    pub async fn reload(&self) -> Result<Option<ReloadDiff>, Box<dyn StdError>> {
+        let mut promo_codes = self.promocodes.lock().await;
 
-        let _: Vec<PromoCode> = serde_json::from_str(&data)?;
+        self.file.reload(|reloaded: Vec<PromoCode>| {
+            validate_promo_codes(&reloaded)?;
 
-        Ok(PromoCodesStore { promocodes_file_path })
+            let diff = ReloadDiff::between(&promo_codes, &reloaded, |p| p.code.clone());
+            *promo_codes = reloaded;
+            Ok(diff)
+        }).await
     }
 
-    pub async fn load_promo_codes(&self) -> Result<Vec<PromoCode>, Box<dyn StdError>> {
-        let mut file = File::open(&self.promocodes_file_path).await?;
-        let mut data = String::new();
-        file.read_to_string(&mut data).await?;
-
-        let promo_codes: Vec<PromoCode> = serde_json::from_str(&data)?;
-        Ok(promo_codes)
+    pub async fn load_promo_codes(&self) -> Vec<PromoCode> {
+        self.promocodes.lock().await.clone()
     }
 
     pub async fn get_promo_codes(&self) -> Result<Vec<PromoCode>, Box<dyn StdError>> {
-        let promo_codes = self.load_promo_codes().await?;
+        let promo_codes = self.load_promo_codes().await;
 
         if promo_codes.is_empty() {
             Err(Box::new(CustomError {
@@ -78,7 +74,7 @@ impl PromoCodesStore {
         }
     }
 
-    pub async fn apply_promo_code(&self, code: &str, total_price: f64) -> Result<(f64, f64), Box<dyn StdError>> {
+    pub async fn apply_promo_code(&self, code: &str, total_price: &Money) -> Result<(Money, Money), Box<dyn StdError>> {
         let promo = self.get_promo_code(code).await?;
 
         let current_time = Utc::now();
@@ -97,8 +93,23 @@ impl PromoCodesStore {
             }));
         }
 
-        let discount = total_price * (promo.discount / 100.0);
-        let new_price = (total_price - discount).max(0.0);
+        let discount = total_price.percentage(promo.discount);
+        let new_price = total_price.checked_sub(&discount)?.non_negative();
         Ok((new_price, discount))
     }
 }
+
+fn validate_promo_codes(promo_codes: &[PromoCode]) -> Result<(), CustomError> {
+    for promo in promo_codes {
+        if promo.available_at.parse::<chrono::DateTime<Utc>>().is_err()
+            || promo.expired_at.parse::<chrono::DateTime<Utc>>().is_err()
+        {
+            return Err(CustomError::new(
+                &format!("Promo code `{}` has an invalid date", promo.code),
+                "INVALID_PROMO_CODE_DATE",
+            ));
+        }
+    }
+
+    Ok(())
+}
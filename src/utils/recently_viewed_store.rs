@@ -0,0 +1,186 @@
+use chrono::Utc;
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::error::Error as StdError;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::utils::file_watcher::ReloadDiff;
+use crate::utils::json_file::JsonFile;
+
+const MAX_GUEST_HISTORIES: usize = 1000;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ViewedProduct {
+    pub product_id: Uuid,
+    pub viewed_at: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RecentlyViewed {
+    pub owner_id: Uuid,
+    #[serde(default)]
+    pub guest: bool,
+    pub items: Vec<ViewedProduct>,
+    pub updated_at: String,
+}
+
+pub struct RecentlyViewedStore {
+    pub recently_viewed: Mutex<Vec<RecentlyViewed>>,
+    pub file: JsonFile,
+    pub limit: usize,
+    dirty: AtomicBool,
+}
+
+impl RecentlyViewedStore {
+    pub async fn new(recently_viewed_file_path: String, limit: usize) -> Result<Self, Box<dyn StdError>> {
+        let (file, recently_viewed): (JsonFile, Vec<RecentlyViewed>) = JsonFile::open(recently_viewed_file_path, "[]").await?;
+
+        Ok(RecentlyViewedStore {
+            recently_viewed: Mutex::new(recently_viewed),
+            file,
+            limit: limit.max(1),
+            dirty: AtomicBool::new(false),
+        })
+    }
+
+    pub async fn save(&self) -> Result<(), Box<dyn StdError>> {
+        let recently_viewed = self.recently_viewed.lock().await;
+        self.file.write(&*recently_viewed).await?;
+        self.dirty.store(false, Ordering::SeqCst);
+        info!("Recently viewed products successfully saved.");
+        Ok(())
+    }
+
+    /// Writes views recorded since the last save.
+    pub async fn flush(&self) -> Result<(), Box<dyn StdError>> {
+        if self.dirty.load(Ordering::SeqCst) {
+            self.save().await?;
+        }
+        Ok(())
+    }
+
+    pub async fn reload(&self) -> Result<Option<ReloadDiff>, Box<dyn StdError>> {
+        let mut recently_viewed = self.recently_viewed.lock().await;
+
+        self.file.reload(|reloaded: Vec<RecentlyViewed>| {
+            let diff = ReloadDiff::between(&recently_viewed, &reloaded, |r| r.owner_id);
+            *recently_viewed = reloaded;
+            self.dirty.store(false, Ordering::SeqCst);
+            Ok(diff)
+        }).await
+    }
+
+    /// Records a view in memory; it is written to disk by the next `flush`.
+    pub async fn record_view(&self, owner_id: Uuid, guest: bool, product_id: Uuid) {
+        let mut recently_viewed = self.recently_viewed.lock().await;
+        let now = Utc::now().to_rfc3339();
+
+        let history = match recently_viewed.iter().position(|r| r.owner_id == owner_id) {
+            Some(pos) if recently_viewed[pos].items.first().is_some_and(|item| item.product_id == product_id) => return,
+            Some(pos) => &mut recently_viewed[pos],
+            None => {
+                recently_viewed.push(RecentlyViewed {
+                    owner_id,
+                    guest,
+                    items: Vec::new(),
+                    updated_at: now.clone(),
+                });
+                recently_viewed.last_mut().unwrap()
+            }
+        };
+
+        history.items.retain(|item| item.product_id != product_id);
+        history.items.insert(0, ViewedProduct { product_id, viewed_at: now.clone() });
+        history.items.truncate(self.limit);
+        history.updated_at = now;
+
+        evict_guest_histories(&mut recently_viewed);
+        self.dirty.store(true, Ordering::SeqCst);
+    }
+
+    pub async fn get_recently_viewed(&self, owner_id: Uuid) -> Vec<ViewedProduct> {
+        let recently_viewed = self.recently_viewed.lock().await;
+        recently_viewed.iter().find(|r| r.owner_id == owner_id).map(|r| r.items.clone()).unwrap_or_default()
+    }
+
+    pub async fn clear(&self, owner_id: Uuid) -> Result<(), Box<dyn StdError>> {
+        let mut recently_viewed = self.recently_viewed.lock().await;
+        let initial_len = recently_viewed.len();
+        recently_viewed.retain(|r| r.owner_id != owner_id);
+
+        if recently_viewed.len() == initial_len {
+            return Ok(());
+        }
+
+        drop(recently_viewed);
+        self.save().await
+    }
+
+    pub async fn merge_guest_history(&self, guest_id: Uuid, user_id: Uuid) -> Result<(), Box<dyn StdError>> {
+        let mut recently_viewed = self.recently_viewed.lock().await;
+
+        let Some(pos) = recently_viewed.iter().position(|r| r.owner_id == guest_id) else {
+            return Ok(());
+        };
+        let guest_history = recently_viewed.remove(pos);
+
+        match recently_viewed.iter_mut().find(|r| r.owner_id == user_id) {
+            Some(history) => {
+                let mut items = guest_history.items;
+                items.append(&mut history.items);
+                items.sort_by(|a, b| b.viewed_at.cmp(&a.viewed_at));
+
+                let mut seen = HashSet::new();
+                items.retain(|item| seen.insert(item.product_id));
+                items.truncate(self.limit);
+
+                history.items = items;
+                history.updated_at = Utc::now().to_rfc3339();
+            }
+            None => recently_viewed.push(RecentlyViewed {
+                owner_id: user_id,
+                guest: false,
+                updated_at: Utc::now().to_rfc3339(),
+                ..guest_history
+            }),
+        }
+
+        drop(recently_viewed);
+        self.save().await
+    }
+}
+
+pub fn spawn_recently_viewed_flusher(recently_viewed_store: Arc<RecentlyViewedStore>, interval: Duration) {
+    actix_web::rt::spawn(async move {
+        loop {
+            actix_web::rt::time::sleep(interval).await;
+
+            if let Err(e) = recently_viewed_store.flush().await {
+                error!("Failed to save recently viewed products: {}", e);
+            }
+        }
+    });
+}
+
+fn evict_guest_histories(recently_viewed: &mut Vec<RecentlyViewed>) {
+    let guests = recently_viewed.iter().filter(|r| r.guest).count();
+
+    if guests <= MAX_GUEST_HISTORIES {
+        return;
+    }
+
+    let mut guest_updates: Vec<String> = recently_viewed
+        .iter()
+        .filter(|r| r.guest)
+        .map(|r| r.updated_at.clone())
+        .collect();
+    guest_updates.sort();
+
+    let cutoff = guest_updates[guests - MAX_GUEST_HISTORIES].clone();
+    recently_viewed.retain(|r| !r.guest || r.updated_at >= cutoff);
+}
@@ -0,0 +1,141 @@
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::state::app_state::AppState;
+use crate::utils::orders_store::{Order, OrderStatus};
+use crate::utils::product_store::ProductView;
+
+const CO_PURCHASE_WEIGHT: f64 = 3.0;
+const ORDERED_SEED_WEIGHT: f64 = 2.0;
+const FAVORITE_SEED_WEIGHT: f64 = 1.0;
+
+#[derive(Serialize)]
+pub struct ProductRecommendations {
+    pub frequently_bought_together: Vec<ProductView>,
+    pub similar: Vec<ProductView>,
+}
+
+#[derive(Default)]
+pub struct Recommender {
+    co_purchases: Mutex<HashMap<Uuid, HashMap<Uuid, u32>>>,
+}
+
+fn order_groups(order: &Order) -> BTreeSet<Uuid> {
+    order.items
+        .iter()
+        .map(|item| item.product.parent.unwrap_or(item.product.uuid))
+        .collect()
+}
+
+fn update_pairs(co_purchases: &mut HashMap<Uuid, HashMap<Uuid, u32>>, order: &Order, add: bool) {
+    let groups = order_groups(order);
+
+    for &a in &groups {
+        for &b in groups.iter().filter(|&&b| b != a) {
+            let counts = co_purchases.entry(a).or_default();
+            let count = counts.entry(b).or_default();
+
+            if add {
+                *count += 1;
+            } else {
+                *count = count.saturating_sub(1);
+                if *count == 0 {
+                    counts.remove(&b);
+                }
+            }
+        }
+    }
+}
+
+fn top(scores: HashMap<Uuid, f64>, limit: usize) -> Vec<Uuid> {
+    let mut scores: Vec<(Uuid, f64)> = scores.into_iter().filter(|(_, score)| *score > 0.0).collect();
+    scores.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    scores.into_iter().take(limit).map(|(uuid, _)| uuid).collect()
+}
+
+impl Recommender {
+    pub async fn rebuild(&self, orders: &[Order]) {
+        let mut co_purchases = HashMap::new();
+
+        for order in orders.iter().filter(|o| o.order_status != OrderStatus::Canceled) {
+            update_pairs(&mut co_purchases, order, true);
+        }
+
+        *self.co_purchases.lock().await = co_purchases;
+    }
+
+    pub async fn record_order(&self, order: &Order) {
+        update_pairs(&mut *self.co_purchases.lock().await, order, true);
+    }
+
+    pub async fn forget_order(&self, order: &Order) {
+        update_pairs(&mut *self.co_purchases.lock().await, order, false);
+    }
+
+    async fn co_purchase_scores(&self, seeds: &[(Uuid, f64)]) -> HashMap<Uuid, f64> {
+        let co_purchases = self.co_purchases.lock().await;
+        let mut scores = HashMap::new();
+
+        for (seed, weight) in seeds {
+            for (&uuid, &count) in co_purchases.get(seed).into_iter().flatten() {
+                *scores.entry(uuid).or_default() += count as f64 * weight;
+            }
+        }
+
+        scores
+    }
+
+    pub async fn for_product(&self, product_id: Uuid, app_state: &AppState, limit: usize) -> ProductRecommendations {
+        let products_store = &app_state.products_store;
+        let seeds = [(product_id, 1.0)];
+        let exclude = HashSet::from([product_id]);
+
+        let together = top(self.co_purchase_scores(&seeds).await, limit);
+        let mut shown: HashSet<Uuid> = together.iter().copied().collect();
+        shown.insert(product_id);
+
+        let similar = top(products_store.similarity_scores(&seeds, &shown).await, limit);
+
+        ProductRecommendations {
+            frequently_bought_together: products_store.product_views(&together, &exclude).await,
+            similar: products_store.product_views(&similar, &exclude).await,
+        }
+    }
+
+    pub async fn for_user(&self, user_id: Uuid, app_state: &AppState, limit: usize) -> Vec<ProductView> {
+        let products_store = &app_state.products_store;
+        let mut seeds: HashMap<Uuid, f64> = HashMap::new();
+
+        for order in app_state.orders_store.get_orders(user_id).await {
+            if order.order_status != OrderStatus::Canceled {
+                for group in order_groups(&order) {
+                    *seeds.entry(group).or_default() += ORDERED_SEED_WEIGHT;
+                }
+            }
+        }
+
+        for product in app_state.favorites_store.get_favorites(user_id).await {
+            *seeds.entry(product.parent.unwrap_or(product.uuid)).or_default() += FAVORITE_SEED_WEIGHT;
+        }
+
+        let seeds: Vec<(Uuid, f64)> = seeds.into_iter().collect();
+        let exclude: HashSet<Uuid> = seeds.iter().map(|(uuid, _)| *uuid).collect();
+
+        let mut scores = products_store.similarity_scores(&seeds, &exclude).await;
+        for (uuid, score) in self.co_purchase_scores(&seeds).await {
+            *scores.entry(uuid).or_default() += score * CO_PURCHASE_WEIGHT;
+        }
+        scores.retain(|uuid, _| !exclude.contains(uuid));
+
+        let mut picked = top(scores, limit);
+        if picked.len() < limit {
+            let mut skip = exclude.clone();
+            skip.extend(picked.iter().copied());
+            picked.extend(products_store.popular_products(&skip, limit - picked.len()).await);
+        }
+
+        products_store.product_views(&picked, &exclude).await
+    }
+}
@@ -0,0 +1,416 @@
+use chrono::Utc;
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error as StdError;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::utils::banned_words_store::BannedWordsStore;
+use crate::utils::error::CustomError;
+use crate::utils::file_watcher::ReloadDiff;
+use crate::utils::json_file::JsonFile;
+use crate::utils::orders_store::OrdersStore;
+use crate::utils::product_store::{ProductStore, ReviewStats};
+
+const MAX_REVIEW_LENGTH: usize = 2000;
+const MAX_REASON_LENGTH: usize = 500;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum ReviewStatus {
+    Pending,
+    #[default]
+    Approved,
+    Rejected,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ReviewReport {
+    pub user_id: Uuid,
+    pub reason: String,
+    pub created_at: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Review {
+    pub uuid: Uuid,
+    pub product_id: Uuid,
+    pub user_id: Uuid,
+    pub author: String,
+    pub rating: u8,
+    pub text: String,
+    pub created_at: String,
+    #[serde(default)]
+    pub status: ReviewStatus,
+    #[serde(default)]
+    pub rejection_reason: Option<String>,
+    #[serde(default)]
+    pub flagged_words: Vec<String>,
+    #[serde(default)]
+    pub reports: Vec<ReviewReport>,
+    #[serde(default)]
+    pub moderated_by: Option<Uuid>,
+    #[serde(default)]
+    pub moderated_at: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct PublicReview {
+    pub uuid: Uuid,
+    pub product_id: Uuid,
+    pub user_id: Uuid,
+    pub author: String,
+    pub rating: u8,
+    pub text: String,
+    pub created_at: String,
+}
+
+impl From<&Review> for PublicReview {
+    fn from(review: &Review) -> Self {
+        PublicReview {
+            uuid: review.uuid,
+            product_id: review.product_id,
+            user_id: review.user_id,
+            author: review.author.clone(),
+            rating: review.rating,
+            text: review.text.clone(),
+            created_at: review.created_at.clone(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ReviewRequest {
+    pub rating: u8,
+    pub text: String,
+}
+
+#[derive(Deserialize)]
+pub struct ModerationFilter {
+    pub status: Option<ReviewStatus>,
+    pub flagged: Option<bool>,
+    pub reported: Option<bool>,
+}
+
+impl ModerationFilter {
+    fn matches(&self, review: &Review) -> bool {
+        if self.status.is_none() && self.flagged.is_none() && self.reported.is_none() {
+            return review.status == ReviewStatus::Pending || !review.reports.is_empty();
+        }
+
+        self.status.is_none_or(|status| review.status == status)
+            && self.flagged.is_none_or(|flagged| review.flagged_words.is_empty() != flagged)
+            && self.reported.is_none_or(|reported| review.reports.is_empty() != reported)
+    }
+}
+
+#[derive(Serialize)]
+pub struct ModerationQueue {
+    pub items: Vec<Review>,
+    pub page: usize,
+    pub limit: usize,
+    pub total: usize,
+    pub total_pages: usize,
+}
+
+#[derive(Serialize)]
+pub struct ReviewPage {
+    pub items: Vec<PublicReview>,
+    pub page: usize,
+    pub limit: usize,
+    pub total: usize,
+    pub total_pages: usize,
+    pub rating: f64,
+}
+
+pub struct ReviewsStore {
+    pub reviews: Mutex<Vec<Review>>,
+    pub file: JsonFile,
+    pub products_store: Arc<ProductStore>,
+    pub orders_store: Arc<OrdersStore>,
+    pub banned_words_store: Arc<BannedWordsStore>,
+}
+
+impl ReviewsStore {
+    pub async fn new(
+        reviews_file_path: String,
+        products_store: Arc<ProductStore>,
+        orders_store: Arc<OrdersStore>,
+        banned_words_store: Arc<BannedWordsStore>,
+    ) -> Result<Self, Box<dyn StdError>> {
+        let (file, reviews): (JsonFile, Vec<Review>) = JsonFile::open(reviews_file_path, "[]").await?;
+
+        Ok(ReviewsStore {
+            reviews: Mutex::new(reviews),
+            file,
+            products_store,
+            orders_store,
+            banned_words_store,
+        })
+    }
+
+    pub async fn save(&self) -> Result<(), Box<dyn StdError>> {
+        let reviews = self.reviews.lock().await;
+        self.file.write(&*reviews).await?;
+        info!("Reviews successfully saved.");
+        Ok(())
+    }
+
+    pub async fn reload(&self) -> Result<Option<ReloadDiff>, Box<dyn StdError>> {
+        let mut reviews = self.reviews.lock().await;
+        let previous: Vec<Uuid> = reviews.iter().map(|r| r.product_id).collect();
+
+        let diff = self.file.reload(|reloaded: Vec<Review>| {
+            let diff = ReloadDiff::between(&reviews, &reloaded, |r| r.uuid);
+            *reviews = reloaded;
+            Ok(diff)
+        }).await?;
+        drop(reviews);
+
+        if diff.is_some() {
+            self.sync_ratings(&previous).await?;
+        }
+        Ok(diff)
+    }
+
+    pub async fn sync_product_ratings(&self) -> Result<(), Box<dyn StdError>> {
+        self.sync_ratings(&[]).await
+    }
+
+    /// Recomputes stats from approved reviews for every product that has a review record, plus `previous`
+    /// (products whose records a reload may have removed). Products nobody has reviewed keep their catalog values.
+    async fn sync_ratings(&self, previous: &[Uuid]) -> Result<(), Box<dyn StdError>> {
+        let reviews = self.reviews.lock().await;
+        let mut totals: HashMap<Uuid, (u32, u32)> = previous
+            .iter()
+            .chain(reviews.iter().map(|r| &r.product_id))
+            .map(|&id| (id, (0, 0)))
+            .collect();
+
+        for review in reviews.iter().filter(|r| r.status == ReviewStatus::Approved) {
+            let (sum, count) = totals.entry(review.product_id).or_default();
+            *sum += review.rating as u32;
+            *count += 1;
+        }
+
+        drop(reviews);
+
+        let stats: HashMap<Uuid, ReviewStats> = totals
+            .into_iter()
+            .map(|(product_id, (sum, count))| (product_id, ReviewStats::new(sum, count)))
+            .collect();
+
+        self.products_store.apply_review_stats(&stats).await
+    }
+
+    pub async fn add_review(
+        &self,
+        user_id: Uuid,
+        author: &str,
+        product_id: Uuid,
+        request: ReviewRequest,
+    ) -> Result<Review, Box<dyn StdError>> {
+        if !(1..=5).contains(&request.rating) {
+            return Err(Box::new(CustomError::new("Rating must be between 1 and 5", "INVALID_REVIEW_RATING")));
+        }
+
+        let text = request.text.trim();
+        if text.is_empty() || text.chars().count() > MAX_REVIEW_LENGTH {
+            return Err(Box::new(CustomError::new(
+                &format!("Review text must be between 1 and {} characters", MAX_REVIEW_LENGTH),
+                "INVALID_REVIEW_TEXT",
+            )));
+        }
+
+        if self.products_store.get_product(product_id).await.is_none() {
+            return Err(Box::new(CustomError::new("Product not found", "PRODUCT_NOT_FOUND")));
+        }
+
+        if !self.orders_store.has_ordered(user_id, product_id).await {
+            return Err(Box::new(CustomError::new(
+                "Only customers who ordered this product can review it",
+                "REVIEW_NOT_ALLOWED",
+            )));
+        }
+
+        let flagged_words = self.banned_words_store.find_matches(text).await;
+        let mut reviews = self.reviews.lock().await;
+
+        if reviews
+            .iter()
+            .any(|r| r.user_id == user_id && r.product_id == product_id && r.status != ReviewStatus::Rejected)
+        {
+            return Err(Box::new(CustomError::new(
+                "You have already reviewed this product",
+                "REVIEW_ALREADY_EXISTS",
+            )));
+        }
+
+        let review = Review {
+            uuid: Uuid::new_v4(),
+            product_id,
+            user_id,
+            author: author.to_string(),
+            rating: request.rating,
+            text: text.to_string(),
+            created_at: Utc::now().to_rfc3339(),
+            status: ReviewStatus::Pending,
+            rejection_reason: None,
+            flagged_words,
+            reports: Vec::new(),
+            moderated_by: None,
+            moderated_at: None,
+        };
+
+        reviews.push(review.clone());
+        drop(reviews);
+
+        self.save().await?;
+        Ok(review)
+    }
+
+    pub async fn report_review(
+        &self,
+        user_id: Uuid,
+        product_id: Uuid,
+        review_id: Uuid,
+        reason: &str,
+    ) -> Result<(), Box<dyn StdError>> {
+        let reason = validate_reason(reason)?;
+        let mut reviews = self.reviews.lock().await;
+
+        let review = reviews
+            .iter_mut()
+            .find(|r| r.uuid == review_id && r.product_id == product_id && r.status == ReviewStatus::Approved)
+            .ok_or_else(|| CustomError::new("Review not found", "REVIEW_NOT_FOUND"))?;
+
+        if review.user_id == user_id {
+            return Err(Box::new(CustomError::new("You cannot report your own review", "CANNOT_REPORT_OWN_REVIEW")));
+        }
+
+        if review.reports.iter().any(|r| r.user_id == user_id) {
+            return Err(Box::new(CustomError::new(
+                "You have already reported this review",
+                "REVIEW_ALREADY_REPORTED",
+            )));
+        }
+
+        review.reports.push(ReviewReport {
+            user_id,
+            reason,
+            created_at: Utc::now().to_rfc3339(),
+        });
+
+        drop(reviews);
+        self.save().await
+    }
+
+    pub async fn moderation_queue(&self, filter: &ModerationFilter, page: usize, limit: usize) -> ModerationQueue {
+        let reviews = self.reviews.lock().await;
+
+        let mut matched: Vec<&Review> = reviews.iter().filter(|r| filter.matches(r)).collect();
+        matched.sort_by(|a, b| {
+            a.flagged_words.is_empty()
+                .cmp(&b.flagged_words.is_empty())
+                .then(b.reports.len().cmp(&a.reports.len()))
+                .then(a.created_at.cmp(&b.created_at))
+        });
+
+        let page = page.max(1);
+        let limit = limit.max(1);
+        let total = matched.len();
+
+        ModerationQueue {
+            items: matched
+                .into_iter()
+                .skip((page - 1) * limit)
+                .take(limit)
+                .cloned()
+                .collect(),
+            page,
+            limit,
+            total,
+            total_pages: total.div_ceil(limit),
+        }
+    }
+
+    pub async fn approve_review(&self, admin_id: Uuid, review_id: Uuid) -> Result<Review, Box<dyn StdError>> {
+        self.moderate_review(admin_id, review_id, ReviewStatus::Approved, None).await
+    }
+
+    pub async fn reject_review(&self, admin_id: Uuid, review_id: Uuid, reason: &str) -> Result<Review, Box<dyn StdError>> {
+        let reason = validate_reason(reason)?;
+        self.moderate_review(admin_id, review_id, ReviewStatus::Rejected, Some(reason)).await
+    }
+
+    async fn moderate_review(
+        &self,
+        admin_id: Uuid,
+        review_id: Uuid,
+        status: ReviewStatus,
+        rejection_reason: Option<String>,
+    ) -> Result<Review, Box<dyn StdError>> {
+        let mut reviews = self.reviews.lock().await;
+
+        let review = reviews
+            .iter_mut()
+            .find(|r| r.uuid == review_id)
+            .ok_or_else(|| CustomError::new("Review not found", "REVIEW_NOT_FOUND"))?;
+
+        review.status = status;
+        review.rejection_reason = rejection_reason;
+        review.reports.clear();
+        review.moderated_by = Some(admin_id);
+        review.moderated_at = Some(Utc::now().to_rfc3339());
+
+        let review = review.clone();
+        drop(reviews);
+
+        self.save().await?;
+        self.sync_product_ratings().await?;
+        Ok(review)
+    }
+
+    pub async fn get_reviews(&self, product_id: Uuid, page: usize, limit: usize) -> ReviewPage {
+        let reviews = self.reviews.lock().await;
+
+        let mut matched: Vec<&Review> = reviews
+            .iter()
+            .filter(|r| r.product_id == product_id && r.status == ReviewStatus::Approved)
+            .collect();
+        matched.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+        let page = page.max(1);
+        let limit = limit.max(1);
+        let total = matched.len();
+        let sum: u32 = matched.iter().map(|r| r.rating as u32).sum();
+
+        ReviewPage {
+            items: matched
+                .into_iter()
+                .skip((page - 1) * limit)
+                .take(limit)
+                .map(PublicReview::from)
+                .collect(),
+            page,
+            limit,
+            total,
+            total_pages: total.div_ceil(limit),
+            rating: ReviewStats::new(sum, total as u32).rating,
+        }
+    }
+}
+
+fn validate_reason(reason: &str) -> Result<String, CustomError> {
+    let reason = reason.trim();
+
+    if reason.is_empty() || reason.chars().count() > MAX_REASON_LENGTH {
+        return Err(CustomError::new(
+            &format!("Reason must be between 1 and {} characters", MAX_REASON_LENGTH),
+            "INVALID_REASON",
+        ));
+    }
+
+    Ok(reason.to_string())
+}
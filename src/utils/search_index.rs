@@ -0,0 +1,533 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::models::product::Product;
+
+const NAME_WEIGHT: f32 = 3.0;
+const BRAND_WEIGHT: f32 = 2.5;
+const TAG_WEIGHT: f32 = 2.0;
+const DESCRIPTION_WEIGHT: f32 = 1.0;
+
+const PREFIX_FACTOR: f32 = 0.8;
+const ONE_TYPO_FACTOR: f32 = 0.6;
+const TWO_TYPOS_FACTOR: f32 = 0.4;
+
+const RUSSIAN_ENDINGS: [&str; 38] = [
+    "ями", "ами", "ого", "его", "ому", "ему", "ыми", "ими", "ых", "их", "ой", "ей", "ий", "ый",
+    "ая", "яя", "ое", "ее", "ую", "юю", "ом", "ем", "ам", "ям", "ах", "ях", "ов", "ев", "ы",
+    "и", "а", "я", "о", "е", "у", "ю", "ь", "й",
+];
+
+struct Posting {
+    doc: usize,
+    weight: f32,
+}
+
+pub struct SearchHit {
+    pub doc: usize,
+    pub score: f32,
+    pub matched_tokens: usize,
+    pub terms: HashSet<String>,
+}
+
+#[derive(Default)]
+pub struct SearchIndex {
+    terms: HashMap<String, Vec<Posting>>,
+    vocabulary: Vec<String>,
+    doc_count: usize,
+}
+
+impl SearchIndex {
+    pub fn build(products: &[Product]) -> Self {
+        let mut terms: HashMap<String, Vec<Posting>> = HashMap::new();
+
+        for (doc, product) in products.iter().enumerate() {
+            let mut frequencies: HashMap<String, f32> = HashMap::new();
+
+            let mut add_field = |text: &str, weight: f32| {
+                let mut counts: HashMap<String, u32> = HashMap::new();
+                for term in tokenize(text) {
+                    *counts.entry(term).or_default() += 1;
+                }
+                for (term, tf) in counts {
+                    *frequencies.entry(term).or_default() += weight * (1.0 + (tf as f32).ln());
+                }
+            };
+
+            add_field(&product.name, NAME_WEIGHT);
+            add_field(&product.brand, BRAND_WEIGHT);
+            add_field(&product.tags.join(" "), TAG_WEIGHT);
+            add_field(product.description.as_deref().unwrap_or_default(), DESCRIPTION_WEIGHT);
+
+            for (term, weight) in frequencies {
+                terms.entry(term).or_default().push(Posting { doc, weight });
+            }
+        }
+
+        let mut vocabulary: Vec<String> = terms.keys().cloned().collect();
+        vocabulary.sort();
+
+        SearchIndex {
+            terms,
+            vocabulary,
+            doc_count: products.len(),
+        }
+    }
+
+    pub fn search(&self, query: &str) -> Vec<SearchHit> {
+        let tokens = tokenize(query);
+        let mut hits: HashMap<usize, SearchHit> = HashMap::new();
+
+        for (i, token) in tokens.iter().enumerate() {
+            let allow_prefix = i == tokens.len() - 1 || token.chars().count() >= 3;
+            let mut best: HashMap<usize, (f32, Vec<&str>)> = HashMap::new();
+
+            for (term, factor) in self.matching_terms(token, allow_prefix) {
+                let postings = &self.terms[term];
+                let idf = (1.0 + self.doc_count as f32 / postings.len() as f32).ln();
+
+                for posting in postings {
+                    let score = factor * idf * posting.weight;
+                    let entry = best.entry(posting.doc).or_insert((0.0, Vec::new()));
+                    if score > entry.0 {
+                        entry.0 = score;
+                    }
+                    entry.1.push(term);
+                }
+            }
+
+            for (doc, (score, matched_terms)) in best {
+                let hit = hits.entry(doc).or_insert_with(|| SearchHit {
+                    doc,
+                    score: 0.0,
+                    matched_tokens: 0,
+                    terms: HashSet::new(),
+                });
+                hit.score += score;
+                hit.matched_tokens += 1;
+                hit.terms.extend(matched_terms.into_iter().map(str::to_string));
+            }
+        }
+
+        let mut hits: Vec<SearchHit> = hits.into_values().collect();
+        hits.sort_by(|a, b| {
+            b.matched_tokens
+                .cmp(&a.matched_tokens)
+                .then(b.score.total_cmp(&a.score))
+                .then(a.doc.cmp(&b.doc))
+        });
+        hits
+    }
+
+    fn terms_with_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a str> {
+        let from = self.vocabulary.partition_point(|term| term.as_str() < prefix);
+
+        self.vocabulary[from..]
+            .iter()
+            .map(String::as_str)
+            .take_while(move |term| term.starts_with(prefix))
+    }
+
+    /// Exact and prefix matches come straight from the sorted vocabulary. Typo tolerance is only a fallback
+    /// and only considers terms that share the first letter and have a similar length.
+    fn matching_terms<'a>(&'a self, token: &'a str, allow_prefix: bool) -> Vec<(&'a str, f32)> {
+        let mut matched = Vec::new();
+
+        if let Some((term, _)) = self.terms.get_key_value(token) {
+            matched.push((term.as_str(), 1.0));
+        }
+
+        if allow_prefix {
+            matched.extend(
+                self.terms_with_prefix(token)
+                    .filter(|term| *term != token)
+                    .map(|term| (term, PREFIX_FACTOR)),
+            );
+        }
+
+        if !matched.is_empty() {
+            return matched;
+        }
+
+        let token_len = token.chars().count();
+        let max_typos = match token_len {
+            0..=3 => return matched,
+            4..=7 => 1,
+            _ => 2,
+        };
+        let initial = &token[..token.chars().next().map_or(0, char::len_utf8)];
+
+        self.terms_with_prefix(initial)
+            .filter(|term| term.chars().count().abs_diff(token_len) <= max_typos)
+            .filter_map(|term| match edit_distance(token, term) {
+                1 => Some((term, ONE_TYPO_FACTOR)),
+                2 if max_typos >= 2 => Some((term, TWO_TYPOS_FACTOR)),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1).min(d[i][j - 1] + 1).min(d[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    d[a.len()][b.len()]
+}
+
+pub fn tokenize(text: &str) -> Vec<String> {
+    words(text)
+        .into_iter()
+        .map(|(start, end)| normalize_word(&text[start..end]))
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+fn words(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = None;
+
+    for (i, c) in text.char_indices() {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                spans.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(s) = start {
+        spans.push((s, text.len()));
+    }
+
+    spans
+}
+
+pub fn normalize_word(word: &str) -> String {
+    let lower = word.to_lowercase().replace('ё', "е");
+
+    if lower.chars().any(is_cyrillic) {
+        transliterate(&stem_russian(&lower))
+    } else {
+        stem_english(&lower)
+    }
+}
+
+fn is_cyrillic(c: char) -> bool {
+    matches!(c, 'а'..='я' | 'ё')
+}
+
+fn stem_russian(word: &str) -> String {
+    let len = word.chars().count();
+
+    for ending in RUSSIAN_ENDINGS {
+        let ending_len = ending.chars().count();
+        if word.ends_with(ending) && len - ending_len >= 3 {
+            return word[..word.len() - ending.len()].to_string();
+        }
+    }
+
+    word.to_string()
+}
+
+fn stem_english(word: &str) -> String {
+    if word.len() > 4 && word.ends_with('s') && !word.ends_with("ss") && !word.ends_with("us") {
+        word[..word.len() - 1].to_string()
+    } else {
+        word.to_string()
+    }
+}
+
+pub fn transliterate(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        let latin = match c {
+            'а' => "a",
+            'б' => "b",
+            'в' => "v",
+            'г' => "g",
+            'д' => "d",
+            'е' | 'ё' | 'э' => "e",
+            'ж' => "zh",
+            'з' => "z",
+            'и' => "i",
+            'й' | 'ы' => "y",
+            'к' => "k",
+            'л' => "l",
+            'м' => "m",
+            'н' => "n",
+            'о' => "o",
+            'п' => "p",
+            'р' => "r",
+            'с' => "s",
+            'т' => "t",
+            'у' => "u",
+            'ф' => "f",
+            'х' => "h",
+            'ц' => "ts",
+            'ч' => "ch",
+            'ш' => "sh",
+            'щ' => "sch",
+            'ъ' | 'ь' => "",
+            'ю' => "yu",
+            'я' => "ya",
+            _ => {
+                result.push(c);
+                continue;
+            }
+        };
+        result.push_str(latin);
+    }
+
+    result
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+pub fn highlight(text: &str, terms: &HashSet<String>) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut last = 0;
+
+    for (start, end) in words(text) {
+        let word = &text[start..end];
+        if terms.contains(&normalize_word(word)) {
+            result.push_str(&escape_html(&text[last..start]));
+            result.push_str("<mark>");
+            result.push_str(&escape_html(word));
+            result.push_str("</mark>");
+            last = end;
+        }
+    }
+
+    result.push_str(&escape_html(&text[last..]));
+    result
+}
+
+pub fn snippet(text: &str, terms: &HashSet<String>, max_chars: usize) -> Option<String> {
+    let spans = words(text);
+    let first = spans.iter().position(|&(s, e)| terms.contains(&normalize_word(&text[s..e])))?;
+    let width = |lo: usize, hi: usize| text[spans[lo].0..spans[hi].1].chars().count();
+
+    let (mut lo, mut hi) = (first, first);
+    loop {
+        let mut grown = false;
+
+        if lo > 0 && width(lo - 1, hi) <= max_chars {
+            lo -= 1;
+            grown = true;
+        }
+        if hi + 1 < spans.len() && width(lo, hi + 1) <= max_chars {
+            hi += 1;
+            grown = true;
+        }
+
+        if !grown {
+            break;
+        }
+    }
+
+    let (start, end) = (spans[lo].0, spans[hi].1);
+
+    let mut result = String::new();
+    if start > 0 {
+        result.push('…');
+    }
+    result.push_str(&highlight(&text[start..end], terms));
+    if end < text.len() {
+        result.push('…');
+    }
+
+    Some(result)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SuggestionKind {
+    Product,
+    Brand,
+    Tag,
+}
+
+struct Suggestion {
+    text: String,
+    kind: SuggestionKind,
+    doc: Option<usize>,
+    weight: f64,
+}
+
+pub struct SuggestionMatch<'a> {
+    pub text: &'a str,
+    pub doc: Option<usize>,
+}
+
+#[derive(Default)]
+pub struct SuggestIndex {
+    keys: Vec<(String, usize, bool)>,
+    suggestions: Vec<Suggestion>,
+}
+
+impl SuggestIndex {
+    pub fn build(products: &[Product]) -> Self {
+        let mut suggestions = Vec::new();
+        let mut brands: HashMap<String, (String, f64)> = HashMap::new();
+        let mut tags: HashMap<String, (String, f64)> = HashMap::new();
+
+        for (doc, product) in products.iter().enumerate() {
+            let popularity = product.reviews.max(0.0) + 1.0;
+
+            suggestions.push(Suggestion {
+                text: product.name.clone(),
+                kind: SuggestionKind::Product,
+                doc: Some(doc),
+                weight: popularity * product.rating.max(1.0),
+            });
+
+            let brand = brands
+                .entry(product.brand.to_lowercase())
+                .or_insert_with(|| (product.brand.clone(), 0.0));
+            brand.1 += popularity;
+
+            for tag in &product.tags {
+                let entry = tags.entry(tag.to_lowercase()).or_insert_with(|| (tag.clone(), 0.0));
+                entry.1 += popularity;
+            }
+        }
+
+        for (kind, group) in [(SuggestionKind::Brand, brands), (SuggestionKind::Tag, tags)] {
+            for (text, weight) in group.into_values() {
+                suggestions.push(Suggestion {
+                    text,
+                    kind,
+                    doc: None,
+                    weight,
+                });
+            }
+        }
+
+        let mut keys = Vec::new();
+        for (i, suggestion) in suggestions.iter().enumerate() {
+            let folded = fold_for_prefix(&suggestion.text);
+            let parts: Vec<&str> = folded.split(' ').collect();
+
+            for start in 0..parts.len() {
+                keys.push((parts[start..].join(" "), i, start == 0));
+            }
+        }
+        keys.sort();
+
+        SuggestIndex { keys, suggestions }
+    }
+
+    pub fn suggest(&self, query: &str, kind: SuggestionKind, limit: usize) -> Vec<SuggestionMatch<'_>> {
+        let prefix = fold_for_prefix(query);
+        if prefix.is_empty() {
+            return Vec::new();
+        }
+
+        let from = self.keys.partition_point(|(key, ..)| key.as_str() < prefix.as_str());
+        let mut best: HashMap<usize, bool> = HashMap::new();
+
+        for (_, i, from_start) in self.keys[from..].iter().take_while(|(key, ..)| key.starts_with(&prefix)) {
+            if self.suggestions[*i].kind == kind {
+                *best.entry(*i).or_default() |= *from_start;
+            }
+        }
+
+        let mut matched: Vec<(bool, usize)> = best.into_iter().map(|(i, from_start)| (from_start, i)).collect();
+        matched.sort_by(|(a_start, a), (b_start, b)| {
+            b_start
+                .cmp(a_start)
+                .then(self.suggestions[*b].weight.total_cmp(&self.suggestions[*a].weight))
+        });
+
+        matched
+            .into_iter()
+            .take(limit)
+            .map(|(_, i)| SuggestionMatch {
+                text: &self.suggestions[i].text,
+                doc: self.suggestions[i].doc,
+            })
+            .collect()
+    }
+}
+
+fn fold_for_prefix(text: &str) -> String {
+    let lower = text.to_lowercase().replace('ё', "е");
+
+    words(&lower)
+        .into_iter()
+        .map(|(start, end)| transliterate(&lower[start..end]))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn product(name: &str) -> Product {
+        Product::test_fixture(name, "100")
+    }
+
+    fn docs(index: &SearchIndex, query: &str) -> Vec<usize> {
+        index.search(query).into_iter().map(|hit| hit.doc).collect()
+    }
+
+    #[test]
+    fn matches_exact_prefix_and_transliterated_terms() {
+        let index = SearchIndex::build(&[
+            product("Смартфон Apple iPhone 13"),
+            product("Планшет Apple iPad Air"),
+        ]);
+
+        assert_eq!(docs(&index, "iphone"), vec![0]);
+        assert_eq!(docs(&index, "ipa"), vec![1]);
+        assert_eq!(docs(&index, "smartfon"), vec![0]);
+        assert_eq!(docs(&index, "смартфоны"), vec![0]);
+    }
+
+    #[test]
+    fn falls_back_to_typos_only_without_exact_or_prefix_matches() {
+        let index = SearchIndex::build(&[product("Планшет"), product("Планшеты и планеты")]);
+
+        let hits = index.search("plansht");
+        assert_eq!(hits.len(), 2);
+        assert!(hits.iter().all(|hit| hit.terms.contains("planshet")));
+
+        assert!(index.search("qlanshet").is_empty());
+        assert!(index.search("ipd").is_empty());
+    }
+
+    #[test]
+    fn snippet_is_centered_on_the_match() {
+        let text = format!("{} iPhone {}", "слово ".repeat(60), "текст ".repeat(60));
+        let terms = HashSet::from([normalize_word("iphone")]);
+
+        let snippet = snippet(&text, &terms, 80).unwrap();
+        assert!(snippet.contains("<mark>iPhone</mark>"));
+        assert!(snippet.starts_with('…') && snippet.ends_with('…'));
+        assert!(snippet.chars().count() <= 80 + "<mark></mark>".len() + 2);
+    }
+}
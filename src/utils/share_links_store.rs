@@ -0,0 +1,287 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::error::Error as StdError;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::utils::error::CustomError;
+use crate::utils::file_watcher::ReloadDiff;
+use crate::utils::json_file::JsonFile;
+
+const MAX_EXPIRY_DAYS: u32 = 90;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ShareKind {
+    Cart,
+    Favorites,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ShareLink {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub kind: ShareKind,
+    pub created_at: String,
+    pub expires_at: String,
+}
+
+impl ShareLink {
+    fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.parse::<DateTime<Utc>>().map_or(true, |expires_at| expires_at <= now)
+    }
+
+    fn payload(&self) -> String {
+        let kind = match self.kind {
+            ShareKind::Cart => "cart",
+            ShareKind::Favorites => "favorites",
+        };
+        format!("{}:{}:{}:{}", self.id, self.user_id, kind, self.expires_at)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ShareLinkRequest {
+    pub kind: ShareKind,
+    pub expires_in_days: Option<u32>,
+}
+
+#[derive(Serialize)]
+pub struct IssuedShareLink {
+    #[serde(flatten)]
+    pub link: ShareLink,
+    pub token: String,
+}
+
+pub struct ShareLinksStore {
+    pub share_links: Mutex<Vec<ShareLink>>,
+    pub file: JsonFile,
+    secret: Vec<u8>,
+    pub expiry_days: u32,
+}
+
+impl ShareLinksStore {
+    pub async fn new(share_links_file_path: String, secret: Option<String>, expiry_days: u32) -> Result<Self, Box<dyn StdError>> {
+        let (file, share_links): (JsonFile, Vec<ShareLink>) = JsonFile::open(share_links_file_path, "[]").await?;
+
+        let secret = match secret {
+            Some(secret) => secret.into_bytes(),
+            None => {
+                warn!("SHARE_LINK_SECRET is not set, share links will stop working after a restart");
+                [Uuid::new_v4().into_bytes(), Uuid::new_v4().into_bytes()].concat()
+            }
+        };
+
+        Ok(ShareLinksStore {
+            share_links: Mutex::new(share_links),
+            file,
+            secret,
+            expiry_days: expiry_days.clamp(1, MAX_EXPIRY_DAYS),
+        })
+    }
+
+    pub async fn save(&self) -> Result<(), Box<dyn StdError>> {
+        let share_links = self.share_links.lock().await;
+        self.file.write(&*share_links).await?;
+        info!("Share links successfully saved.");
+        Ok(())
+    }
+
+    pub async fn reload(&self) -> Result<Option<ReloadDiff>, Box<dyn StdError>> {
+        let mut share_links = self.share_links.lock().await;
+
+        self.file.reload(|reloaded: Vec<ShareLink>| {
+            let diff = ReloadDiff::between(&share_links, &reloaded, |l| l.id);
+            *share_links = reloaded;
+            Ok(diff)
+        }).await
+    }
+
+    fn mac(&self, link: &ShareLink) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(link.payload().as_bytes());
+        mac
+    }
+
+    fn issue(&self, link: ShareLink) -> IssuedShareLink {
+        let signature = URL_SAFE_NO_PAD.encode(self.mac(&link).finalize().into_bytes());
+        let token = format!("{}.{}", link.id.simple(), signature);
+        IssuedShareLink { link, token }
+    }
+
+    pub async fn create(&self, user_id: Uuid, request: &ShareLinkRequest) -> Result<IssuedShareLink, Box<dyn StdError>> {
+        let days = request.expires_in_days.unwrap_or(self.expiry_days);
+        if days == 0 || days > MAX_EXPIRY_DAYS {
+            return Err(Box::new(CustomError::new(
+                &format!("Share link expiry must be between 1 and {} days", MAX_EXPIRY_DAYS),
+                "INVALID_SHARE_LINK_EXPIRY",
+            )));
+        }
+
+        let now = Utc::now();
+        let link = ShareLink {
+            id: Uuid::new_v4(),
+            user_id,
+            kind: request.kind,
+            created_at: now.to_rfc3339(),
+            expires_at: (now + Duration::days(days as i64)).to_rfc3339(),
+        };
+
+        let mut share_links = self.share_links.lock().await;
+        share_links.retain(|l| !l.is_expired(now));
+        share_links.push(link.clone());
+
+        drop(share_links);
+        self.save().await?;
+        Ok(self.issue(link))
+    }
+
+    pub async fn get_user_links(&self, user_id: Uuid) -> Vec<IssuedShareLink> {
+        let now = Utc::now();
+        let share_links = self.share_links.lock().await;
+
+        share_links
+            .iter()
+            .filter(|l| l.user_id == user_id && !l.is_expired(now))
+            .map(|l| self.issue(l.clone()))
+            .collect()
+    }
+
+    pub async fn revoke(&self, user_id: Uuid, link_id: Uuid) -> Result<(), Box<dyn StdError>> {
+        let mut share_links = self.share_links.lock().await;
+        let initial_len = share_links.len();
+        share_links.retain(|l| !(l.id == link_id && l.user_id == user_id));
+
+        if share_links.len() == initial_len {
+            return Err(Box::new(CustomError::new("Share link not found", "SHARE_LINK_NOT_FOUND")));
+        }
+
+        drop(share_links);
+        self.save().await
+    }
+
+    pub async fn resolve(&self, token: &str) -> Result<ShareLink, CustomError> {
+        let invalid = || CustomError::new("Share link is invalid or has been revoked", "INVALID_SHARE_TOKEN");
+
+        let (id, signature) = token.split_once('.').ok_or_else(invalid)?;
+        let id = Uuid::parse_str(id).map_err(|_| invalid())?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).map_err(|_| invalid())?;
+
+        let link = {
+            let share_links = self.share_links.lock().await;
+            share_links.iter().find(|l| l.id == id).cloned().ok_or_else(invalid)?
+        };
+
+        self.mac(&link).verify_slice(&signature).map_err(|_| invalid())?;
+
+        if link.is_expired(Utc::now()) {
+            return Err(CustomError::new("Share link has expired", "SHARE_LINK_EXPIRED"));
+        }
+
+        Ok(link)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestStore {
+        store: ShareLinksStore,
+        path: std::path::PathBuf,
+    }
+
+    impl Drop for TestStore {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+
+    async fn store(secret: &str) -> TestStore {
+        let path = std::env::temp_dir().join(format!("share-links-{}.json", Uuid::new_v4()));
+        let store = ShareLinksStore::new(path.to_string_lossy().to_string(), Some(secret.to_string()), 7)
+            .await
+            .unwrap();
+        TestStore { store, path }
+    }
+
+    fn request(expires_in_days: Option<u32>) -> ShareLinkRequest {
+        ShareLinkRequest { kind: ShareKind::Cart, expires_in_days }
+    }
+
+    fn error_code(result: Result<ShareLink, CustomError>) -> String {
+        result.err().map(|e| e.error_code).unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn issued_tokens_resolve_until_revoked() {
+        let test = store("secret").await;
+        let user_id = Uuid::new_v4();
+        let issued = test.store.create(user_id, &request(None)).await.unwrap();
+
+        let link = test.store.resolve(&issued.token).await.unwrap();
+        assert_eq!(link.id, issued.link.id);
+        assert_eq!(link.user_id, user_id);
+
+        test.store.revoke(user_id, link.id).await.unwrap();
+        assert_eq!(error_code(test.store.resolve(&issued.token).await), "INVALID_SHARE_TOKEN");
+    }
+
+    #[tokio::test]
+    async fn rejects_malformed_tampered_and_foreign_tokens() {
+        let test = store("secret").await;
+        let issued = test.store.create(Uuid::new_v4(), &request(None)).await.unwrap();
+        let (id, signature) = issued.token.split_once('.').unwrap();
+
+        let first = if signature.starts_with('A') { 'B' } else { 'A' };
+        let tampered = format!("{}{}", first, &signature[1..]);
+
+        let other = store("other-secret").await;
+        other.store.share_links.lock().await.push(issued.link.clone());
+        let foreign = other.store.issue(issued.link.clone()).token;
+
+        for token in ["", "no-separator", &format!("{}.", id), &format!("{}.{}", id, tampered), &format!("{}.{}", Uuid::new_v4().simple(), signature), &foreign] {
+            assert_eq!(error_code(test.store.resolve(token).await), "INVALID_SHARE_TOKEN", "token `{}`", token);
+        }
+    }
+
+    #[tokio::test]
+    async fn editing_the_stored_expiry_invalidates_the_token() {
+        let test = store("secret").await;
+        let issued = test.store.create(Uuid::new_v4(), &request(Some(1))).await.unwrap();
+
+        test.store.share_links.lock().await[0].expires_at = (Utc::now() + Duration::days(365)).to_rfc3339();
+        assert_eq!(error_code(test.store.resolve(&issued.token).await), "INVALID_SHARE_TOKEN");
+    }
+
+    #[tokio::test]
+    async fn expired_links_are_reported_as_expired() {
+        let test = store("secret").await;
+        let link = ShareLink {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            kind: ShareKind::Favorites,
+            created_at: (Utc::now() - Duration::days(2)).to_rfc3339(),
+            expires_at: (Utc::now() - Duration::days(1)).to_rfc3339(),
+        };
+        test.store.share_links.lock().await.push(link.clone());
+
+        let token = test.store.issue(link).token;
+        assert_eq!(error_code(test.store.resolve(&token).await), "SHARE_LINK_EXPIRED");
+    }
+
+    #[tokio::test]
+    async fn rejects_expiry_outside_the_allowed_range() {
+        let test = store("secret").await;
+
+        for days in [0, MAX_EXPIRY_DAYS + 1] {
+            let error = test.store.create(Uuid::new_v4(), &request(Some(days))).await.err().unwrap();
+            assert_eq!(error.downcast_ref::<CustomError>().unwrap().error_code, "INVALID_SHARE_LINK_EXPIRY");
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::BTreeMap;
+
+const MAX_KEY_LENGTH: usize = 40;
+const MAX_VALUE_LENGTH: usize = 100;
+
+struct Unit {
+    names: &'static [&'static str],
+    key: &'static str,
+    suffix: &'static str,
+}
+
+const UNITS: [Unit; 9] = [
+    Unit { names: &["\"", "дюйм", "дюйма", "дюймов", "inch", "inches"], key: "screen_size", suffix: "\"" },
+    Unit { names: &["вт", "w"], key: "power", suffix: " W" },
+    Unit { names: &["мач", "mah"], key: "battery", suffix: " mAh" },
+    Unit { names: &["гц", "hz"], key: "refresh_rate", suffix: " Hz" },
+    Unit { names: &["мп", "mp"], key: "camera", suffix: " MP" },
+    Unit { names: &["гб", "gb"], key: "memory", suffix: " GB" },
+    Unit { names: &["тб", "tb"], key: "storage", suffix: " TB" },
+    Unit { names: &["бар", "atm"], key: "water_resistance", suffix: " bar" },
+    Unit { names: &["дб", "db"], key: "loudness", suffix: " dB" },
+];
+
+pub fn parse_specs(description: &str) -> BTreeMap<String, String> {
+    let mut specs = BTreeMap::new();
+
+    for segment in description.split(['\n', ';']) {
+        let Some((key, value)) = segment.split_once(':') else {
+            continue;
+        };
+        let (key, value) = (key.trim().to_lowercase(), value.trim());
+
+        if key.is_empty() || key.chars().count() > MAX_KEY_LENGTH || key.contains('.') {
+            continue;
+        }
+        if value.is_empty() || value.chars().count() > MAX_VALUE_LENGTH {
+            continue;
+        }
+
+        specs.entry(key).or_insert_with(|| value.to_string());
+    }
+
+    let tokens: Vec<&str> = description.split_whitespace().collect();
+
+    for (i, token) in tokens.iter().enumerate() {
+        let token = token.trim_start_matches(|c: char| c.is_ascii_punctuation() || c == '«');
+        let number_len = token
+            .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))
+            .unwrap_or(token.len());
+        let number = token[..number_len].trim_end_matches(['.', ',']);
+
+        if number.is_empty() {
+            continue;
+        }
+
+        let rest = &token[number.len()..];
+        let unit = match rest.is_empty() {
+            true => tokens.get(i + 1).copied().unwrap_or_default(),
+            false => rest,
+        };
+        let unit = unit
+            .trim_end_matches(|c: char| c.is_ascii_punctuation() && c != '"')
+            .to_lowercase();
+
+        if let Some(unit) = UNITS.iter().find(|u| u.names.contains(&unit.as_str())) {
+            specs
+                .entry(unit.key.to_string())
+                .or_insert_with(|| format!("{}{}", number.replace(',', "."), unit.suffix));
+        }
+    }
+
+    specs
+}
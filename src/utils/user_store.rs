@@ -1,41 +1,24 @@
-use tokio::fs::{File, OpenOptions, create_dir_all};
-use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader};
-use std::path::Path;
 use tokio::sync::Mutex;
 use std::error::Error as StdError;
 use log::info;
 use uuid::Uuid;
 
 use crate::models::user::User;
+use crate::utils::file_watcher::ReloadDiff;
+use crate::utils::json_file::JsonFile;
 
 pub struct UserStore {
     pub users: Mutex<Vec<User>>,
-    pub users_file_path: String,
+    pub file: JsonFile,
 }
 
 impl UserStore {
     pub async fn new(users_file_path: String) -> Result<Self, Box<dyn StdError>> {
-        let path = Path::new(&users_file_path);
-
-        if let Some(parent) = path.parent() {
-            create_dir_all(parent).await.expect("Failed to create directories for users.json file");
-        }
-
-        if !path.exists() {
-            let mut file = File::create(path).await.expect("Failed to create users.json file");
-            file.write_all(b"[]").await.expect("Failed to write empty array to file");
-        }
-
-        let file = File::open(path).await.expect("Failed to open users.json file");
-        let mut reader = BufReader::new(file);
-        let mut data = String::new();
-        reader.read_to_string(&mut data).await.expect("Failed to read file");
-
-        let users: Vec<User> = serde_json::from_str(&data)?;
+        let (file, users): (JsonFile, Vec<User>) = JsonFile::open(users_file_path, "[]").await?;
 
         Ok(UserStore {
             users: Mutex::new(users),
-            users_file_path,
+            file,
         })
     }
 
@@ -52,21 +35,21 @@ impl UserStore {
             }
         }
     
-        let data = serde_json::to_string_pretty(&*users)?;
-        
-        let mut file = OpenOptions::new()
-            .write(true)
-            .truncate(true)
-            .create(true)
-            .open(&self.users_file_path)
-            .await
-            .expect("Failed to open users.json file for writing");
-    
-        file.write_all(data.as_bytes()).await?;
+        self.file.write(&*users).await?;
         info!("Users successfully saved.");
         Ok(())
     }
 
+    pub async fn reload(&self) -> Result<Option<ReloadDiff>, Box<dyn StdError>> {
+        let mut users = self.users.lock().await;
+
+        self.file.reload(|reloaded: Vec<User>| {
+            let diff = ReloadDiff::between(&users, &reloaded, |u| u.id);
+            *users = reloaded;
+            Ok(diff)
+        }).await
+    }
+
     pub async fn add_user(&self, user: User) -> Result<(), Box<dyn StdError>> {
         info!("Locking users to add new user...");
         
@@ -87,9 +70,7 @@ impl UserStore {
 
     pub async fn find_user_by_username(&self, username: &str) -> Option<User> {
         let users = self.users.lock().await;
-        let result = users.iter()
-            .cloned()
-            .find(|user| user.username == username);
+        let result = users.iter().find(|&user| user.username == username).cloned();
     
         if result.is_some() {
             info!("User found for: {}", username);
@@ -102,9 +83,7 @@ impl UserStore {
 
     pub async fn find_user_by_login_or_email(&self, login_or_email: &str) -> Option<User> {
         let users = self.users.lock().await;
-        let result = users.iter()
-            .cloned()
-            .find(|user| user.login == login_or_email || user.email == login_or_email);
+        let result = users.iter().find(|&user| user.login == login_or_email || user.email == login_or_email).cloned();
     
         if result.is_some() {
             info!("User found for: {}", login_or_email);